@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use advent::error::{Error, Result};
+use advent::input;
+use advent::solution::Part;
+
+/// The recorded answers live next to the inputs.
+pub fn path() -> PathBuf {
+    input::root().join("answers.txt")
+}
+
+/// Known answers by day and part, read from lines like `5 2 123`. Empty lines and lines
+/// starting with `#` are ignored.
+#[derive(Debug, PartialEq)]
+pub struct Answers {
+    answers: HashMap<(u32, Part), String>,
+}
+
+impl Answers {
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut answers = HashMap::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = || Error::parse("expected '<day> <part> <answer>'").at_line(index + 1);
+
+            let mut fields = line.splitn(3, char::is_whitespace);
+            let day = fields
+                .next()
+                .and_then(|day| day.parse::<u32>().ok())
+                .ok_or_else(error)?;
+            let part = match fields.next() {
+                Some("1") => Part::One,
+                Some("2") => Part::Two,
+                _ => return Err(error()),
+            };
+            let answer = fields
+                .next()
+                .map(str::trim)
+                .filter(|answer| !answer.is_empty())
+                .ok_or_else(error)?;
+
+            answers.insert((day, part), answer.to_string());
+        }
+
+        Ok(Answers { answers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers = "# day part answer\n1 1 11\n\n1 2 31\n3 1 hello world\n"
+            .parse::<Answers>()
+            .unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some("11"));
+        assert_eq!(answers.get(1, Part::Two), Some("31"));
+        assert_eq!(answers.get(3, Part::One), Some("hello world"));
+        assert_eq!(answers.get(3, Part::Two), None);
+    }
+
+    #[test]
+    fn invalid() {
+        let e = "1 1 11\n1 3 31".parse::<Answers>().unwrap_err();
+        assert_eq!(e.to_string(), "2: expected '<day> <part> <answer>'");
+
+        let e = "1 1".parse::<Answers>().unwrap_err();
+        assert_eq!(e.line, Some(1));
+    }
+}
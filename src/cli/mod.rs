@@ -0,0 +1,382 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use advent::input::{INPUTS_VAR, STDIN};
+use advent::solution::Part;
+
+pub const USAGE: &str = "usage: advent [bench | verify] [all | latest | <day> [<part>]] \
+                         [--input <file>] [--runs <n>] [--format text|json|csv]\n       \
+                         advent new <day>";
+
+/// Number of repetitions for `bench` when `--runs` is not given.
+pub const DEFAULT_RUNS: usize = 10;
+
+/// Explains where the input files are looked up, shown after [`USAGE`].
+pub fn help() -> String {
+    format!(
+        "inputs are read from <root>/day<N>/input.txt and verify compares against <root>/answers.txt,\n\
+         where <root> is ${} or the crate's src directory; --input {} reads the input from stdin",
+        INPUTS_VAR, STDIN
+    )
+}
+
+/// Everything given on the command line.
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub command: Command,
+    pub selection: Selection,
+    /// Input file to use instead of the day's default, only valid for a single day. `-` is stdin.
+    pub input: Option<PathBuf>,
+    pub format: Format,
+}
+
+/// How the answers are printed, the structured formats give one record per part.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Solve and print the answers.
+    Run,
+    /// Time parsing and each part over a number of runs.
+    Bench { runs: usize },
+    /// Compare the answers with the recorded ones.
+    Verify,
+    /// Generate and register a new day from the template.
+    New { day: u32 },
+}
+
+/// What to run, as given on the command line.
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    All,
+    Latest,
+    Day { day: u32, part: Option<Part> },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UsageError {
+    MissingDay,
+    InvalidDay(String),
+    InvalidPart(String),
+    TooManyArguments,
+    UnknownOption(String),
+    MissingValue(&'static str),
+    InvalidValue(&'static str, String),
+    InputNeedsDay,
+    RunsNeedsBench,
+    InputWithVerify,
+    FormatWithBench,
+    OptionsWithNew,
+}
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UsageError::MissingDay => write!(f, "missing the day"),
+            UsageError::InvalidDay(day) => write!(f, "'{}' is not a day", day),
+            UsageError::InvalidPart(part) => write!(f, "'{}' is not a part, expected 1 or 2", part),
+            UsageError::TooManyArguments => write!(f, "too many arguments"),
+            UsageError::UnknownOption(option) => write!(f, "unknown option '{}'", option),
+            UsageError::MissingValue(option) => write!(f, "{} needs a value", option),
+            UsageError::InvalidValue(option, value) => {
+                write!(f, "'{}' is not a valid value for {}", value, option)
+            }
+            UsageError::InputNeedsDay => write!(f, "--input can only be used with a single day"),
+            UsageError::RunsNeedsBench => write!(f, "--runs can only be used with bench"),
+            UsageError::InputWithVerify => write!(f, "--input cannot be used with verify"),
+            UsageError::FormatWithBench => write!(f, "--format cannot be used with bench"),
+            UsageError::OptionsWithNew => write!(f, "new does not take any options"),
+        }
+    }
+}
+
+pub fn parse(args: &[String]) -> Result<Options, UsageError> {
+    let mut input = None;
+    let mut runs = None;
+    let mut format = None;
+    let mut positional = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => {
+                let path = args.next().ok_or(UsageError::MissingValue("--input"))?;
+                input = Some(PathBuf::from(path));
+            }
+            "--runs" => {
+                let n = args.next().ok_or(UsageError::MissingValue("--runs"))?;
+                runs = Some(
+                    n.parse::<usize>()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| UsageError::InvalidValue("--runs", n.clone()))?,
+                );
+            }
+            "--format" => {
+                let f = args.next().ok_or(UsageError::MissingValue("--format"))?;
+                format = Some(match f.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    _ => return Err(UsageError::InvalidValue("--format", f.clone())),
+                });
+            }
+            option if option.starts_with("--") => {
+                return Err(UsageError::UnknownOption(option.to_string()))
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    if positional.first().map(String::as_str) == Some("new") {
+        if input.is_some() || runs.is_some() || format.is_some() {
+            return Err(UsageError::OptionsWithNew);
+        }
+        let day = match &positional[1..] {
+            [day] => parse_day(day)?,
+            [] => return Err(UsageError::MissingDay),
+            _ => return Err(UsageError::TooManyArguments),
+        };
+        return Ok(Options {
+            command: Command::New { day },
+            selection: Selection::Day { day, part: None },
+            input: None,
+            format: Format::Text,
+        });
+    }
+
+    let command = match positional.first().map(String::as_str) {
+        Some("bench") => {
+            positional.remove(0);
+            if format.is_some() {
+                return Err(UsageError::FormatWithBench);
+            }
+            Command::Bench {
+                runs: runs.unwrap_or(DEFAULT_RUNS),
+            }
+        }
+        _ if runs.is_some() => return Err(UsageError::RunsNeedsBench),
+        Some("verify") => {
+            positional.remove(0);
+            if input.is_some() {
+                return Err(UsageError::InputWithVerify);
+            }
+            Command::Verify
+        }
+        _ => Command::Run,
+    };
+
+    let selection = parse_selection(&positional)?;
+
+    if input.is_some() && !matches!(selection, Selection::Day { .. }) {
+        return Err(UsageError::InputNeedsDay);
+    }
+
+    Ok(Options {
+        command,
+        selection,
+        input,
+        format: format.unwrap_or_default(),
+    })
+}
+
+fn parse_selection(args: &[String]) -> Result<Selection, UsageError> {
+    match args {
+        [] => Ok(Selection::All),
+        [s] if s == "all" => Ok(Selection::All),
+        [s] if s == "latest" => Ok(Selection::Latest),
+        [day] => Ok(Selection::Day {
+            day: parse_day(day)?,
+            part: None,
+        }),
+        [day, part] => Ok(Selection::Day {
+            day: parse_day(day)?,
+            part: Some(parse_part(part)?),
+        }),
+        _ => Err(UsageError::TooManyArguments),
+    }
+}
+
+fn parse_day(s: &str) -> Result<u32, UsageError> {
+    s.parse::<u32>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| UsageError::InvalidDay(s.to_string()))
+}
+
+fn parse_part(s: &str) -> Result<Part, UsageError> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(UsageError::InvalidPart(s.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn selection() {
+        assert_eq!(parse_selection(&args(&[])), Ok(Selection::All));
+        assert_eq!(parse_selection(&args(&["all"])), Ok(Selection::All));
+        assert_eq!(parse_selection(&args(&["latest"])), Ok(Selection::Latest));
+        assert_eq!(
+            parse_selection(&args(&["5"])),
+            Ok(Selection::Day { day: 5, part: None })
+        );
+        assert_eq!(
+            parse_selection(&args(&["5", "2"])),
+            Ok(Selection::Day {
+                day: 5,
+                part: Some(Part::Two)
+            })
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            parse_selection(&args(&["x"])),
+            Err(UsageError::InvalidDay("x".to_string()))
+        );
+        assert_eq!(
+            parse_selection(&args(&["26"])),
+            Err(UsageError::InvalidDay("26".to_string()))
+        );
+        assert_eq!(
+            parse_selection(&args(&["5", "3"])),
+            Err(UsageError::InvalidPart("3".to_string()))
+        );
+        assert_eq!(
+            parse_selection(&args(&["5", "1", "1"])),
+            Err(UsageError::TooManyArguments)
+        );
+    }
+
+    #[test]
+    fn input() {
+        assert_eq!(
+            parse(&args(&["3", "--input", "other.txt"])),
+            Ok(Options {
+                command: Command::Run,
+                selection: Selection::Day { day: 3, part: None },
+                input: Some(PathBuf::from("other.txt")),
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            parse(&args(&["3", "--input"])),
+            Err(UsageError::MissingValue("--input"))
+        );
+        assert_eq!(
+            parse(&args(&["all", "--input", "other.txt"])),
+            Err(UsageError::InputNeedsDay)
+        );
+        assert_eq!(
+            parse(&args(&["3", "--verbose"])),
+            Err(UsageError::UnknownOption("--verbose".to_string()))
+        );
+    }
+
+    #[test]
+    fn bench() {
+        assert_eq!(
+            parse(&args(&["bench"])),
+            Ok(Options {
+                command: Command::Bench { runs: DEFAULT_RUNS },
+                selection: Selection::All,
+                input: None,
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            parse(&args(&["bench", "5", "2", "--runs", "3"])),
+            Ok(Options {
+                command: Command::Bench { runs: 3 },
+                selection: Selection::Day {
+                    day: 5,
+                    part: Some(Part::Two)
+                },
+                input: None,
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            parse(&args(&["bench", "--runs", "0"])),
+            Err(UsageError::InvalidValue("--runs", "0".to_string()))
+        );
+        assert_eq!(
+            parse(&args(&["5", "--runs", "3"])),
+            Err(UsageError::RunsNeedsBench)
+        );
+    }
+
+    #[test]
+    fn verify() {
+        assert_eq!(
+            parse(&args(&["verify", "latest"])),
+            Ok(Options {
+                command: Command::Verify,
+                selection: Selection::Latest,
+                input: None,
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            parse(&args(&["verify", "3", "--input", "other.txt"])),
+            Err(UsageError::InputWithVerify)
+        );
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(
+            parse(&args(&["--format", "json"])).map(|options| options.format),
+            Ok(Format::Json)
+        );
+        assert_eq!(
+            parse(&args(&["verify", "--format", "csv"])).map(|options| options.format),
+            Ok(Format::Csv)
+        );
+        assert_eq!(
+            parse(&args(&["--format", "xml"])),
+            Err(UsageError::InvalidValue("--format", "xml".to_string()))
+        );
+        assert_eq!(
+            parse(&args(&["bench", "--format", "json"])),
+            Err(UsageError::FormatWithBench)
+        );
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            parse(&args(&["new", "7"])).map(|options| options.command),
+            Ok(Command::New { day: 7 })
+        );
+        assert_eq!(parse(&args(&["new"])), Err(UsageError::MissingDay));
+        assert_eq!(
+            parse(&args(&["new"])).unwrap_err().to_string(),
+            "missing the day"
+        );
+        assert_eq!(
+            parse(&args(&["new", "x"])),
+            Err(UsageError::InvalidDay("x".to_string()))
+        );
+        assert_eq!(
+            parse(&args(&["new", "7", "--format", "json"])),
+            Err(UsageError::OptionsWithNew)
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How repeatedly applying a step function to a state ends. States are numbered by the number
+/// of steps taken to reach them, the initial state is state 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome<S> {
+    /// The step function gave no next state for state `steps`, which is `last`.
+    Terminated { steps: usize, last: S },
+    /// State `start + length` equals state `start`, from there on every state repeats.
+    Cycle { start: usize, length: usize },
+}
+
+/// Runs `step` from `initial` until it returns `None` or a state repeats, remembering every
+/// state in a hash map. Needs one pass and finds the cycle as soon as it closes.
+pub fn find_cycle<S>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Outcome<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut state = initial;
+
+    for i in 0.. {
+        if let Some(&start) = seen.get(&state) {
+            return Outcome::Cycle {
+                start,
+                length: i - start,
+            };
+        }
+        seen.insert(state.clone(), i);
+        state = match step(&state) {
+            Some(next) => next,
+            None => {
+                return Outcome::Terminated {
+                    steps: i,
+                    last: state,
+                }
+            }
+        };
+    }
+
+    unreachable!()
+}
+
+/// Like [`find_cycle`], but keeps only a few states instead of all of them, using Floyd's
+/// tortoise and hare. `step` is called about three times as often.
+pub fn find_cycle_floyd<S>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Outcome<S>
+where
+    S: Clone + Eq,
+{
+    let (mut tortoise, mut hare) = (initial.clone(), initial.clone());
+    let mut steps = 0;
+
+    loop {
+        for _ in 0..2 {
+            hare = match step(&hare) {
+                Some(next) => next,
+                None => return Outcome::Terminated { steps, last: hare },
+            };
+            steps += 1;
+        }
+        tortoise = step(&tortoise).expect("the tortoise follows the hare");
+        if tortoise == hare {
+            break;
+        }
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise).expect("the cycle repeats");
+        hare = step(&hare).expect("the cycle repeats");
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise).expect("the cycle repeats");
+    while tortoise != hare {
+        hare = step(&hare).expect("the cycle repeats");
+        length += 1;
+    }
+
+    Outcome::Cycle { start, length }
+}
+
+/// Like [`find_cycle_floyd`], but using Brent's algorithm, which needs fewer steps.
+pub fn find_cycle_brent<S>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Outcome<S>
+where
+    S: Clone + Eq,
+{
+    let mut tortoise = initial.clone();
+    let Some(mut hare) = step(&initial) else {
+        return Outcome::Terminated {
+            steps: 0,
+            last: initial,
+        };
+    };
+    let (mut steps, mut power, mut length) = (1, 1, 1);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = match step(&hare) {
+            Some(next) => next,
+            None => return Outcome::Terminated { steps, last: hare },
+        };
+        steps += 1;
+        length += 1;
+    }
+
+    let (mut tortoise, mut hare) = (initial.clone(), initial);
+    for _ in 0..length {
+        hare = step(&hare).expect("the cycle repeats");
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise).expect("the cycle repeats");
+        hare = step(&hare).expect("the cycle repeats");
+        start += 1;
+    }
+
+    Outcome::Cycle { start, length }
+}
+
+/// State `n`, found without taking `n` steps once the states start repeating. `None` when
+/// `step` stops before state `n`.
+pub fn state_at<S>(initial: S, n: usize, mut step: impl FnMut(&S) -> Option<S>) -> Option<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen = HashMap::<S, usize>::new();
+    let mut history = Vec::<S>::new();
+    let mut state = initial;
+
+    for i in 0..=n {
+        if let Some(&start) = seen.get(&state) {
+            return Some(history[start + (n - start) % (i - start)].clone());
+        }
+        if i == n {
+            break;
+        }
+        seen.insert(state.clone(), i);
+        history.push(state.clone());
+        state = step(&state)?;
+    }
+
+    Some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1, 2, 4, 8, 6, 2, 4, ...
+    fn doubling(x: &u64) -> Option<u64> {
+        Some(x * 2 % 10)
+    }
+
+    fn counting(x: &u64) -> Option<u64> {
+        (*x < 5).then_some(x + 1)
+    }
+
+    #[test]
+    fn cycles() {
+        let cycle = Outcome::Cycle {
+            start: 1,
+            length: 4,
+        };
+
+        assert_eq!(find_cycle(1, doubling), cycle);
+        assert_eq!(find_cycle_floyd(1, doubling), cycle);
+        assert_eq!(find_cycle_brent(1, doubling), cycle);
+
+        let fixed = Outcome::Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(find_cycle(0, doubling), fixed);
+        assert_eq!(find_cycle_floyd(0, doubling), fixed);
+        assert_eq!(find_cycle_brent(0, doubling), fixed);
+    }
+
+    #[test]
+    fn terminates() {
+        let terminated = Outcome::Terminated { steps: 5, last: 5 };
+
+        assert_eq!(find_cycle(0, counting), terminated);
+        assert_eq!(find_cycle_floyd(0, counting), terminated);
+        assert_eq!(find_cycle_brent(0, counting), terminated);
+        assert_eq!(
+            find_cycle_brent(5, counting),
+            Outcome::Terminated { steps: 0, last: 5 }
+        );
+    }
+
+    #[test]
+    fn extrapolate() {
+        assert_eq!(state_at(1, 0, doubling), Some(1));
+        assert_eq!(state_at(1, 3, doubling), Some(8));
+        assert_eq!(state_at(1, 10, doubling), Some(4));
+        assert_eq!(state_at(1, 1_000_000_000_000, doubling), Some(6));
+
+        assert_eq!(state_at(0, 5, counting), Some(5));
+        assert_eq!(state_at(0, 6, counting), None);
+    }
+}
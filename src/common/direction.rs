@@ -0,0 +1,282 @@
+use std::fmt;
+
+use super::Point;
+use crate::error::{Error, Result};
+
+/// One of the eight compass directions, `North` is towards smaller `y`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rotation {
+    CW,
+    CCW,
+}
+
+impl Direction {
+    /// All eight directions, clockwise starting at north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The four orthogonal directions, clockwise starting at north.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Iterates [`Direction::ALL`].
+    pub fn all() -> impl Iterator<Item = Direction> {
+        Direction::ALL.into_iter()
+    }
+
+    /// Iterates [`Direction::CARDINAL`].
+    pub fn cardinals() -> impl Iterator<Item = Direction> {
+        Direction::CARDINAL.into_iter()
+    }
+
+    pub fn is_cardinal(&self) -> bool {
+        Cardinal::try_from(*self).is_ok()
+    }
+
+    /// Turns `steps` times 45 degrees clockwise, negative steps turn counter-clockwise.
+    pub fn turn(&self, steps: i32) -> Direction {
+        Direction::ALL[(*self as i32 + steps).rem_euclid(8) as usize]
+    }
+
+    /// Turns 45 degrees.
+    pub fn rotate(&self, rotation: &Rotation) -> Direction {
+        self.turn(rotation.sign())
+    }
+
+    /// Turns 90 degrees.
+    pub fn rotate90(&self, rotation: &Rotation) -> Direction {
+        self.turn(2 * rotation.sign())
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.turn(4)
+    }
+
+    /// The step taken when moving one position in this direction.
+    pub fn offset(&self) -> Point {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        };
+        Point { x, y }
+    }
+}
+
+impl Rotation {
+    fn sign(&self) -> i32 {
+        match self {
+            Rotation::CW => 1,
+            Rotation::CCW => -1,
+        }
+    }
+}
+
+/// One of the four orthogonal directions, for walkers that can never face a diagonal.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Cardinal {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Cardinal {
+    /// All four, clockwise starting at north.
+    pub const ALL: [Cardinal; 4] = [
+        Cardinal::North,
+        Cardinal::East,
+        Cardinal::South,
+        Cardinal::West,
+    ];
+
+    /// Turns `steps` times 90 degrees clockwise, negative steps turn counter-clockwise.
+    pub fn turn(&self, steps: i32) -> Cardinal {
+        Cardinal::ALL[(*self as i32 + steps).rem_euclid(4) as usize]
+    }
+
+    /// Turns 90 degrees.
+    pub fn rotate(&self, rotation: &Rotation) -> Cardinal {
+        self.turn(rotation.sign())
+    }
+
+    pub fn opposite(&self) -> Cardinal {
+        self.turn(2)
+    }
+
+    /// The step taken when moving one position in this direction.
+    pub fn offset(&self) -> Point {
+        Direction::from(*self).offset()
+    }
+}
+
+impl From<Cardinal> for Direction {
+    fn from(value: Cardinal) -> Self {
+        Direction::CARDINAL[value as usize]
+    }
+}
+
+/// Fails with the direction itself when it is a diagonal.
+impl TryFrom<Direction> for Cardinal {
+    type Error = Direction;
+
+    fn try_from(value: Direction) -> std::result::Result<Self, Self::Error> {
+        match value {
+            Direction::North => Ok(Cardinal::North),
+            Direction::East => Ok(Cardinal::East),
+            Direction::South => Ok(Cardinal::South),
+            Direction::West => Ok(Cardinal::West),
+            diagonal => Err(diagonal),
+        }
+    }
+}
+
+/// Accepts arrows (`^>v<` and `↑↗→↘↓↙←↖`), compass letters (`NESW`) and `UDLR`.
+impl TryFrom<char> for Direction {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self> {
+        Ok(match value {
+            '^' | '↑' | 'N' | 'U' => Direction::North,
+            '↗' => Direction::NorthEast,
+            '>' | '→' | 'E' | 'R' => Direction::East,
+            '↘' => Direction::SouthEast,
+            'v' | '↓' | 'S' | 'D' => Direction::South,
+            '↙' => Direction::SouthWest,
+            '<' | '←' | 'W' | 'L' => Direction::West,
+            '↖' => Direction::NorthWest,
+            _ => return Err(Error::parse(format!("'{}' is not a direction", value))),
+        })
+    }
+}
+
+impl Direction {
+    /// The arrow this direction renders as, `^>v<` for the cardinal directions.
+    pub fn arrow(&self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::NorthEast => '↗',
+            Direction::East => '>',
+            Direction::SouthEast => '↘',
+            Direction::South => 'v',
+            Direction::SouthWest => '↙',
+            Direction::West => '<',
+            Direction::NorthWest => '↖',
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+/// Like the conversion for [`Direction`], diagonal arrows are rejected.
+impl TryFrom<char> for Cardinal {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self> {
+        Cardinal::try_from(Direction::try_from(value)?)
+            .map_err(|_| Error::parse(format!("'{}' is not a cardinal direction", value)))
+    }
+}
+
+impl fmt::Display for Cardinal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Direction::from(*self).arrow())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate() {
+        assert_eq!(Direction::North.rotate(&Rotation::CW), Direction::NorthEast);
+        assert_eq!(
+            Direction::North.rotate(&Rotation::CCW),
+            Direction::NorthWest
+        );
+        assert_eq!(Direction::West.rotate90(&Rotation::CW), Direction::North);
+        assert_eq!(
+            Direction::NorthEast.rotate90(&Rotation::CCW),
+            Direction::NorthWest
+        );
+        assert_eq!(Direction::South.turn(-3), Direction::NorthEast);
+        assert_eq!(Direction::South.turn(11), Direction::NorthWest);
+        assert!(Direction::all().all(|d| d.opposite().opposite() == d && d.opposite() != d));
+        assert!(Direction::all().all(|d| d.offset() == -d.opposite().offset()));
+    }
+
+    #[test]
+    fn cardinal() {
+        assert_eq!(Direction::cardinals().count(), 4);
+        assert!(Direction::cardinals().all(|d| d.is_cardinal()));
+        assert_eq!(Direction::all().filter(Direction::is_cardinal).count(), 4);
+
+        assert_eq!(Cardinal::try_from(Direction::East), Ok(Cardinal::East));
+        assert_eq!(
+            Cardinal::try_from(Direction::SouthWest),
+            Err(Direction::SouthWest)
+        );
+        assert_eq!(Direction::from(Cardinal::West), Direction::West);
+
+        assert_eq!(Cardinal::West.rotate(&Rotation::CW), Cardinal::North);
+        assert_eq!(Cardinal::North.turn(-1), Cardinal::West);
+        assert_eq!(Cardinal::South.opposite(), Cardinal::North);
+        assert_eq!(Cardinal::South.offset(), Point { x: 0, y: 1 });
+    }
+
+    #[test]
+    fn characters() {
+        assert_eq!(Direction::try_from('v').unwrap(), Direction::South);
+        assert_eq!(Direction::try_from('N').unwrap(), Direction::North);
+        assert_eq!(Direction::try_from('L').unwrap(), Direction::West);
+        assert_eq!(Direction::try_from('↘').unwrap(), Direction::SouthEast);
+        assert_eq!(
+            Direction::try_from('x').err().unwrap().to_string(),
+            "'x' is not a direction"
+        );
+
+        assert!(Direction::all().all(|d| { Direction::try_from(d.arrow()).unwrap() == d }));
+
+        assert_eq!(Cardinal::try_from('R').unwrap(), Cardinal::East);
+        assert_eq!(Cardinal::East.to_string(), ">");
+        assert_eq!(
+            Cardinal::try_from('↖').err().unwrap().to_string(),
+            "'↖' is not a cardinal direction"
+        );
+    }
+}
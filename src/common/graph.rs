@@ -0,0 +1,420 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+
+/// Directed graph over nodes of any hashable type, such as ordering rules between page numbers.
+/// Nodes are kept in the order they were first added, which makes every result deterministic.
+#[derive(Clone, Debug)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    successors: Vec<Vec<usize>>,
+    edges: usize,
+}
+
+/// Edges that form a cycle, each one starts where the previous one ends and the last one ends
+/// where the first starts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<N> {
+    pub edges: Vec<(N, N)>,
+}
+
+impl<N: fmt::Display> fmt::Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (from, _) in &self.edges {
+            write!(f, "{} -> ", from)?;
+        }
+        match self.edges.first() {
+            Some((first, _)) => write!(f, "{}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            successors: Vec::new(),
+            edges: 0,
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    /// Adds `node` when it is not in the graph yet, returns its index.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&i) = self.index.get(&node) {
+            return i;
+        }
+        self.nodes.push(node.clone());
+        self.successors.push(Vec::new());
+        self.index.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    /// Adds an edge from `from` to `to`, adding the nodes as needed. Duplicate edges are ignored.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        if !self.successors[from].contains(&to) {
+            self.successors[from].push(to);
+            self.edges += 1;
+        }
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.index.contains_key(node)
+    }
+
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        match (self.index.get(from), self.index.get(to)) {
+            (Some(&from), Some(&to)) => self.successors[from].contains(&to),
+            _ => false,
+        }
+    }
+
+    /// The nodes `node` has an edge to, in the order the edges were added.
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.index
+            .get(node)
+            .into_iter()
+            .flat_map(|&i| self.successors[i].iter().map(|&j| &self.nodes[j]))
+    }
+
+    /// Every node that can be reached from `node` by following one or more edges.
+    pub fn reachable_from(&self, node: &N) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut todo = self.index.get(node).map_or(Vec::new(), |&i| vec![i]);
+
+        while let Some(i) = todo.pop() {
+            for &j in &self.successors[i] {
+                if seen.insert(j) {
+                    todo.push(j);
+                }
+            }
+        }
+
+        seen.into_iter().map(|i| self.nodes[i].clone()).collect()
+    }
+
+    /// True when `to` can be reached from `from` by following one or more edges.
+    pub fn reaches(&self, from: &N, to: &N) -> bool {
+        self.reachable_from(from).contains(to)
+    }
+
+    /// All nodes ordered so that every edge points forward, or a cycle that makes that
+    /// impossible. Of the nodes that could go next, the one added first does.
+    pub fn topological_sort(&self) -> Result<Vec<N>, Cycle<N>> {
+        let subset = (0..self.nodes.len()).map(Some).collect::<Vec<_>>();
+        Ok(self
+            .sort_positions(&subset)?
+            .into_iter()
+            .map(|position| self.nodes[position].clone())
+            .collect())
+    }
+
+    /// Like [`Graph::topological_sort`], but only for `subset` and the edges between its nodes.
+    /// Nodes that are not in the graph have no edges, and a node that occurs more than once is
+    /// placed once for every occurrence. Of the nodes that could go next, the one earliest in
+    /// `subset` does.
+    pub fn topological_sort_of(&self, subset: &[N]) -> Result<Vec<N>, Cycle<N>> {
+        let indices = subset
+            .iter()
+            .map(|node| self.index.get(node).copied())
+            .collect::<Vec<_>>();
+        Ok(self
+            .sort_positions(&indices)?
+            .into_iter()
+            .map(|position| subset[position].clone())
+            .collect())
+    }
+
+    /// Kahn's algorithm on the positions of `subset`, which holds node indices or `None` for
+    /// nodes without edges. There is an edge between two positions when there is one between
+    /// their nodes. Returns the positions in sorted order.
+    fn sort_positions(&self, subset: &[Option<usize>]) -> Result<Vec<usize>, Cycle<N>> {
+        let mut positions = HashMap::<usize, Vec<usize>>::new();
+        for (position, i) in subset.iter().enumerate() {
+            if let Some(i) = i {
+                positions.entry(*i).or_default().push(position);
+            }
+        }
+        // The positions a position with node `i` has an edge to.
+        let positions = &positions;
+        let successors = |i: Option<usize>| {
+            i.into_iter()
+                .flat_map(|i| &self.successors[i])
+                .flat_map(move |j| positions.get(j).into_iter().flatten().copied())
+        };
+
+        let mut incoming = vec![0; subset.len()];
+        for &i in subset {
+            successors(i).for_each(|q| incoming[q] += 1);
+        }
+
+        let mut ready = (0..subset.len())
+            .filter(|&p| incoming[p] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut sorted = Vec::with_capacity(subset.len());
+
+        while let Some(Reverse(p)) = ready.pop() {
+            sorted.push(p);
+            for q in successors(subset[p]) {
+                incoming[q] -= 1;
+                if incoming[q] == 0 {
+                    ready.push(Reverse(q));
+                }
+            }
+        }
+
+        if sorted.len() == subset.len() {
+            Ok(sorted)
+        } else {
+            // Every position left has an edge from another one left, so their nodes contain a
+            // cycle.
+            let left = (0..subset.len())
+                .filter(|&p| incoming[p] > 0)
+                .filter_map(|p| subset[p])
+                .collect::<HashSet<_>>();
+            Err(self
+                .cycle_within(&left)
+                .expect("unsorted nodes contain a cycle"))
+        }
+    }
+
+    /// Some cycle in the graph, `None` when it is acyclic.
+    pub fn find_cycle(&self) -> Option<Cycle<N>> {
+        self.cycle_within(&(0..self.nodes.len()).collect())
+    }
+
+    /// Depth-first search for an edge back onto the current path, using only `nodes`.
+    fn cycle_within(&self, nodes: &HashSet<usize>) -> Option<Cycle<N>> {
+        let mut done = HashSet::new();
+        let mut starts = nodes.iter().copied().collect::<Vec<_>>();
+        starts.sort();
+
+        for start in starts {
+            if done.contains(&start) {
+                continue;
+            }
+            // The path from `start` with, for every node on it, how many successors were tried.
+            let mut path = vec![(start, 0)];
+            let mut on_path = HashSet::from([start]);
+
+            while let Some((i, tried)) = path.last_mut() {
+                let i = *i;
+                let next = self.successors[i][*tried..]
+                    .iter()
+                    .position(|j| nodes.contains(j) && !done.contains(j));
+                match next {
+                    Some(offset) => {
+                        *tried += offset + 1;
+                        let j = self.successors[i][*tried - 1];
+                        if on_path.contains(&j) {
+                            let from = path.iter().position(|(k, _)| *k == j).unwrap();
+                            let cycle = path[from..]
+                                .iter()
+                                .map(|(k, _)| *k)
+                                .chain([j])
+                                .collect::<Vec<_>>();
+                            return Some(Cycle {
+                                edges: cycle
+                                    .windows(2)
+                                    .map(|w| (self.nodes[w[0]].clone(), self.nodes[w[1]].clone()))
+                                    .collect(),
+                            });
+                        }
+                        on_path.insert(j);
+                        path.push((j, 0));
+                    }
+                    None => {
+                        on_path.remove(&i);
+                        done.insert(i);
+                        path.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Groups of nodes that can all reach each other, every node is in exactly one of them. The
+    /// components come in topological order, edges between them only point forward.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        // Kosaraju: order the nodes by when a depth-first search finishes them, then collect
+        // components on the reversed graph in reverse finishing order.
+        let n = self.nodes.len();
+        let mut finished = Vec::with_capacity(n);
+        let mut seen = vec![false; n];
+
+        for start in 0..n {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut stack = vec![(start, 0)];
+            while let Some((i, next)) = stack.last_mut() {
+                let i = *i;
+                match self.successors[i].get(*next) {
+                    Some(&j) => {
+                        *next += 1;
+                        if !seen[j] {
+                            seen[j] = true;
+                            stack.push((j, 0));
+                        }
+                    }
+                    None => {
+                        finished.push(i);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        let mut predecessors = vec![Vec::new(); n];
+        for (i, successors) in self.successors.iter().enumerate() {
+            successors.iter().for_each(|&j| predecessors[j].push(i));
+        }
+
+        let mut component = vec![None; n];
+        let mut components = Vec::new();
+        for &start in finished.iter().rev() {
+            if component[start].is_some() {
+                continue;
+            }
+            component[start] = Some(components.len());
+            let mut members = vec![start];
+            let mut todo = vec![start];
+            while let Some(i) = todo.pop() {
+                for &j in &predecessors[i] {
+                    if component[j].is_none() {
+                        component[j] = Some(components.len());
+                        members.push(j);
+                        todo.push(j);
+                    }
+                }
+            }
+            members.sort();
+            components.push(members.into_iter().map(|i| self.nodes[i].clone()).collect());
+        }
+
+        components
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for Graph<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(edges: I) -> Self {
+        let mut graph = Graph::new();
+        edges
+            .into_iter()
+            .for_each(|(from, to)| graph.add_edge(from, to));
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let graph = Graph::from_iter([(1, 2), (1, 3), (3, 2), (1, 2)]);
+
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 3));
+        assert!(graph.has_edge(&3, &2) && !graph.has_edge(&2, &3));
+        assert_eq!(graph.successors(&1).collect::<Vec<_>>(), [&2, &3]);
+        assert_eq!(graph.successors(&7).count(), 0);
+    }
+
+    #[test]
+    fn sort() {
+        let graph = Graph::from_iter([("c", "a"), ("b", "a"), ("c", "b"), ("a", "d")]);
+
+        assert_eq!(graph.topological_sort(), Ok(vec!["c", "b", "a", "d"]));
+        assert_eq!(
+            graph.topological_sort_of(&["a", "c", "e"]),
+            Ok(vec!["c", "a", "e"])
+        );
+        assert_eq!(graph.find_cycle(), None);
+    }
+
+    #[test]
+    fn sort_repeated() {
+        let graph = Graph::from_iter([(1, 2)]);
+
+        assert_eq!(graph.topological_sort_of(&[2, 1, 2]), Ok(vec![1, 2, 2]));
+        assert_eq!(graph.topological_sort_of(&[2, 3, 2]), Ok(vec![2, 3, 2]));
+
+        let graph = Graph::from_iter([(1, 2), (2, 1)]);
+        assert_eq!(graph.topological_sort_of(&[1, 1]), Ok(vec![1, 1]));
+        assert_eq!(
+            graph
+                .topological_sort_of(&[2, 1, 2])
+                .unwrap_err()
+                .to_string(),
+            "1 -> 2 -> 1"
+        );
+
+        let graph = Graph::from_iter([(1, 1)]);
+        assert_eq!(
+            graph.topological_sort_of(&[1, 1]).unwrap_err().edges,
+            [(1, 1)]
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let graph = Graph::from_iter([(1, 2), (2, 3), (3, 4), (4, 2), (4, 5)]);
+
+        let cycle = graph.topological_sort().unwrap_err();
+        assert_eq!(cycle.edges, [(2, 3), (3, 4), (4, 2)]);
+        assert_eq!(cycle.to_string(), "2 -> 3 -> 4 -> 2");
+        assert_eq!(graph.find_cycle(), Some(cycle));
+
+        // Without 3 the remaining nodes are ordered fine.
+        assert_eq!(
+            graph.topological_sort_of(&[5, 4, 2, 1]),
+            Ok(vec![4, 5, 1, 2])
+        );
+    }
+
+    #[test]
+    fn components() {
+        let graph = Graph::from_iter([(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4), (6, 6)]);
+
+        assert_eq!(
+            graph.strongly_connected_components(),
+            [vec![6], vec![1, 2, 3], vec![4, 5]]
+        );
+    }
+
+    #[test]
+    fn reachability() {
+        let graph = Graph::from_iter([(1, 2), (2, 3), (4, 1)]);
+
+        assert_eq!(graph.reachable_from(&1), HashSet::from([2, 3]));
+        assert!(graph.reaches(&4, &3));
+        assert!(!graph.reaches(&3, &4));
+        assert!(!graph.reaches(&1, &1));
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use super::{Bounds, Direction, GridView, Point, Rect, Rotation};
+use crate::error::{Error, Result};
+
+/// Dense rectangular grid with its origin at (0, 0), stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses equally long lines, as produced by [`super::import`], mapping every character with
+    /// `f`. The positions of the `markers` characters are returned as well, every marker has an
+    /// entry even when it does not occur. Characters `f` rejects are reported with their line and
+    /// column.
+    pub fn parse_with<E>(
+        lines: &[String],
+        markers: &[char],
+        mut f: impl FnMut(char) -> std::result::Result<T, E>,
+    ) -> Result<(Self, HashMap<char, Vec<Point>>)>
+    where
+        E: fmt::Display,
+    {
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        let mut found: HashMap<char, Vec<Point>> =
+            markers.iter().map(|&marker| (marker, Vec::new())).collect();
+
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate().take(width) {
+                if let Some(positions) = found.get_mut(&c) {
+                    positions.push(Point {
+                        x: x as i64,
+                        y: y as i64,
+                    });
+                }
+                cells.push(
+                    f(c).map_err(|e| Error::parse(e.to_string()).at_line(y + 1).at_column(x + 1))?,
+                );
+            }
+
+            let length = line.chars().count();
+            if length != width {
+                return Err(Error::parse(format!(
+                    "expected {} characters but found {}",
+                    width, length
+                ))
+                .at_line(y + 1));
+            }
+        }
+
+        Ok((Grid::new(width, lines.len(), cells), found))
+    }
+
+    /// Grid from cells in row-major order, there must be exactly `width * height` of them.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "cells do not fill the grid");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Grid with every cell computed from its position by `f`, in row-major order.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..width * height)
+            .map(|i| {
+                f(Point {
+                    x: (i % width) as i64,
+                    y: (i / width) as i64,
+                })
+            })
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The area covered by the cells, `None` for an empty grid.
+    pub fn bounds(&self) -> Option<Rect> {
+        (!self.is_empty()).then(|| {
+            Rect::new(
+                Point { x: 0, y: 0 },
+                Point {
+                    x: self.width as i64 - 1,
+                    y: self.height as i64 - 1,
+                },
+            )
+        })
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        (0..self.width as i64).contains(&p.x) && (0..self.height as i64).contains(&p.y)
+    }
+
+    fn index_of(&self, p: &Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    /// The cell at `p`, `None` outside the grid.
+    pub fn get(&self, p: &Point) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: &Point) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// All positions in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.len()).map(move |i| Point {
+            x: (i % width) as i64,
+            y: (i / width) as i64,
+        })
+    }
+
+    /// All cells with their position in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.points().zip(self.cells.iter_mut())
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Cells of column `x` from top to bottom, empty when `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Lines running down and to the right, starting with the one in the top right corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width)
+            .rev()
+            .map(|x| (x, 0))
+            .chain((1..self.height).map(|y| (0, y)));
+        starts.map(|start| self.line(start, Direction::SouthEast))
+    }
+
+    /// Lines running down and to the left, starting with the one in the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last = self.width.saturating_sub(1);
+        let starts = (0..self.width)
+            .map(|x| (x, 0))
+            .chain((1..self.height).map(move |y| (last, y)));
+        starts.map(|start| self.line(start, Direction::SouthWest))
+    }
+
+    /// Cells from `start` in `direction` up to the edge of the grid.
+    fn line(&self, (x, y): (usize, usize), direction: Direction) -> impl Iterator<Item = &T> {
+        let start = Point {
+            x: x as i64,
+            y: y as i64,
+        };
+        std::iter::successors(Some(start), move |p| Some(p.r#move(&direction)))
+            .map_while(|p| self.get(&p))
+    }
+
+    /// Borrowed view of the cells within `rect`, clipped to the grid. `None` when they do not
+    /// overlap.
+    pub fn view(&self, rect: &Rect) -> Option<GridView<'_, T>> {
+        GridView::new(self, rect)
+    }
+
+    /// Position of the first cell, in row-major order, matching `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, c)| predicate(c)).map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Text with one line per row, each cell drawn by `f`.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&f).chain(['\n']).collect::<String>())
+            .collect()
+    }
+}
+
+/// Transformations into a new grid.
+impl<T: Clone> Grid<T> {
+    /// Rows become columns, the cell at (x, y) moves to (y, x).
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |p| self.cell(p.y, p.x))
+    }
+
+    /// Turns the grid a quarter, a clockwise turn puts the first row in the last column.
+    pub fn rotate90(&self, rotation: &Rotation) -> Grid<T> {
+        let (w, h) = (self.width as i64, self.height as i64);
+        match rotation {
+            Rotation::CW => Grid::from_fn(self.height, self.width, |p| self.cell(p.y, h - 1 - p.x)),
+            Rotation::CCW => {
+                Grid::from_fn(self.height, self.width, |p| self.cell(w - 1 - p.y, p.x))
+            }
+        }
+    }
+
+    pub fn rotate180(&self) -> Grid<T> {
+        let mut cells = self.cells.clone();
+        cells.reverse();
+        Grid::new(self.width, self.height, cells)
+    }
+
+    /// Mirrors left to right, every row is reversed.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let w = self.width as i64;
+        Grid::from_fn(self.width, self.height, |p| self.cell(w - 1 - p.x, p.y))
+    }
+
+    /// Mirrors top to bottom, the rows are reversed.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let h = self.height as i64;
+        Grid::from_fn(self.width, self.height, |p| self.cell(p.x, h - 1 - p.y))
+    }
+
+    fn cell(&self, x: i64, y: i64) -> T {
+        self[&Point { x, y }].clone()
+    }
+}
+
+impl<T> Bounds for Grid<T> {
+    fn contains(&self, p: &Point) -> bool {
+        Grid::contains(self, p)
+    }
+}
+
+impl<T> Index<&Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: &Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", p))
+    }
+}
+
+impl<T> IndexMut<&Point> for Grid<T> {
+    fn index_mut(&mut self, p: &Point) -> &mut T {
+        let i = self
+            .index_of(p)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", p));
+        &mut self.cells[i]
+    }
+}
+
+/// Grid of the characters in the lines produced by [`super::import`], all lines must be
+/// equally long.
+impl TryFrom<&[String]> for Grid<char> {
+    type Error = Error;
+
+    fn try_from(lines: &[String]) -> Result<Self> {
+        Grid::parse_with(lines, &[], Ok::<_, Infallible>).map(|(grid, _)| grid)
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|c| *c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = Grid::try_from(lines(&["abc", "def"]).as_slice()).unwrap();
+
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid.get(&Point { x: 2, y: 1 }), Some(&'f'));
+        assert_eq!(grid[&Point { x: 1, y: 0 }], 'b');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn uneven() {
+        let e = Grid::try_from(lines(&["abc", "de"]).as_slice()).unwrap_err();
+
+        assert_eq!(e.to_string(), "2: expected 3 characters but found 2");
+    }
+
+    #[test]
+    fn bounds() {
+        let mut grid = Grid::filled(2, 3, 0);
+
+        assert_eq!(grid.get(&Point { x: -1, y: 0 }), None);
+        assert_eq!(grid.get(&Point { x: 2, y: 0 }), None);
+        assert_eq!(grid.get(&Point { x: 0, y: 3 }), None);
+        assert_eq!(
+            grid.bounds().map(|rect| rect.area()),
+            Some(grid.len() as i64)
+        );
+        assert_eq!(Grid::<u8>::new(0, 0, vec![]).bounds(), None);
+
+        *grid.get_mut(&Point { x: 1, y: 2 }).unwrap() = 7;
+        grid[&Point { x: 0, y: 0 }] = 1;
+
+        assert_eq!(grid.position(|&c| c == 7), Some(Point { x: 1, y: 2 }));
+        assert_eq!(grid.render(|c| char::from(b'0' + *c as u8)), "10\n00\n07\n");
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            grid.columns().map(|c| c.sum::<i32>()).collect::<Vec<_>>(),
+            [5, 7, 9]
+        );
+        assert_eq!(grid.points().last(), Some(Point { x: 2, y: 1 }));
+    }
+
+    #[test]
+    fn parse_with_markers() {
+        let (grid, markers) = Grid::parse_with(&lines(&["#.S", "S.#"]), &['S', 'E'], |c| match c {
+            '#' => Ok(true),
+            '.' | 'S' => Ok(false),
+            _ => Err(format!("unexpected '{}'", c)),
+        })
+        .unwrap();
+
+        assert_eq!(
+            grid.render(|&wall| if wall { '#' } else { '.' }),
+            "#..\n..#\n"
+        );
+        assert_eq!(markers[&'S'], [Point { x: 2, y: 0 }, Point { x: 0, y: 1 }]);
+        assert!(markers[&'E'].is_empty());
+    }
+
+    #[test]
+    fn parse_with_unmapped() {
+        let e = Grid::parse_with(&lines(&["#..", ".x#"]), &[], |c| match c {
+            '#' | '.' => Ok(c),
+            _ => Err(format!("unexpected '{}'", c)),
+        })
+        .unwrap_err();
+
+        assert_eq!(e.to_string(), "2:2: unexpected 'x'");
+    }
+
+    #[test]
+    fn transform() {
+        let grid = Grid::try_from(lines(&["abc", "def"]).as_slice()).unwrap();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate90(&Rotation::CW).to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate90(&Rotation::CCW).to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate180().to_string(), "fed\ncba\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(
+            grid.rotate90(&Rotation::CW).rotate90(&Rotation::CW),
+            grid.rotate180()
+        );
+    }
+
+    #[test]
+    fn diagonals() {
+        let grid = Grid::try_from(lines(&["abc", "def"]).as_slice()).unwrap();
+
+        assert_eq!(
+            grid.diagonals()
+                .map(|line| line.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["c", "bf", "ae", "d"]
+        );
+        assert_eq!(
+            grid.anti_diagonals()
+                .map(|line| line.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["a", "bd", "ce", "f"]
+        );
+    }
+}
@@ -1,81 +1,83 @@
-use std::fs;
+use std::{fmt, fs, io::Read, ops, path::Path, str::FromStr};
 
-pub fn import(name: &str) -> Vec<String> {
-    fs::read_to_string(name)
-        .unwrap()
+use crate::error::{Error, Result};
+
+pub use cycle::{find_cycle, find_cycle_brent, find_cycle_floyd, state_at, Outcome};
+pub use direction::{Cardinal, Direction, Rotation};
+pub use graph::{Cycle, Graph};
+pub use grid::Grid;
+pub use numbers::{integers, parse_integers, split_integers, split_tuple};
+pub use rect::Rect;
+pub use region::{Labels, Region};
+pub use search::{astar, bfs, dijkstra, Paths};
+pub use sparse::SparseGrid;
+pub use view::GridView;
+pub use visited::{Visited, VisitedDirections};
+
+mod cycle;
+mod direction;
+mod graph;
+mod grid;
+mod numbers;
+mod rect;
+mod region;
+mod search;
+mod sparse;
+mod view;
+mod visited;
+
+pub fn import(name: impl AsRef<Path>) -> Result<Vec<String>> {
+    let name = name.as_ref();
+
+    Ok(split_lines(
+        &fs::read_to_string(name).map_err(|e| Error::from(e).in_file(name))?,
+    ))
+}
+
+/// Like [`import`], but reads everything from `reader`, e.g. stdin.
+pub fn import_from(mut reader: impl Read) -> Result<Vec<String>> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    Ok(split_lines(&input))
+}
+
+/// Splits on `\n` without a trailing empty line, the `\r` of CRLF line endings is removed too.
+pub fn split_lines(input: &str) -> Vec<String> {
+    input
         .split_terminator('\n')
-        .map(|s| s.to_string())
+        .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
         .collect()
 }
 
-#[derive(Clone, PartialEq)]
-pub enum Direction {
-    North,
-    NorthEast,
-    East,
-    SouthEast,
-    South,
-    SouthWest,
-    West,
-    NorthWest,
-}
-
-pub enum Rotation {
-    CW,
-    CCW,
-}
-
-impl Direction {
-    pub fn r#rotate(&self, rotation: &Rotation) -> Direction {
-        match self {
-            Direction::North => match rotation {
-                Rotation::CW => Direction::NorthEast,
-                Rotation::CCW => Direction::NorthWest,
-            },
-            Direction::NorthEast => match rotation {
-                Rotation::CW => Direction::East,
-                Rotation::CCW => Direction::North,
-            },
-
-            Direction::East => match rotation {
-                Rotation::CW => Direction::SouthEast,
-                Rotation::CCW => Direction::NorthEast,
-            },
-            Direction::SouthEast => match rotation {
-                Rotation::CW => Direction::South,
-                Rotation::CCW => Direction::East,
-            },
-
-            Direction::South => match rotation {
-                Rotation::CW => Direction::SouthWest,
-                Rotation::CCW => Direction::SouthEast,
-            },
-            Direction::SouthWest => match rotation {
-                Rotation::CW => Direction::West,
-                Rotation::CCW => Direction::South,
-            },
-            Direction::West => match rotation {
-                Rotation::CW => Direction::NorthWest,
-                Rotation::CCW => Direction::SouthWest,
-            },
-            Direction::NorthWest => match rotation {
-                Rotation::CW => Direction::North,
-                Rotation::CCW => Direction::West,
-            },
-        }
-    }
+/// Parses every line, errors get the line number (starting at 1) they occurred on.
+pub fn parse_lines<T>(input: &[String]) -> Result<Vec<T>>
+where
+    T: FromStr<Err = Error>,
+{
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, line)| line.parse::<T>().map_err(|e| e.at_line(i + 1)))
+        .collect()
 }
 
-#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+/// A position or offset, `y` grows downwards. Points order row-major: by `y`, then by `x`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub struct Point {
     pub x: i64,
     pub y: i64,
 }
 
 impl Point {
-    // pub fn manhattan(&self, rhs: &Self) -> i64 {
-    //     (rhs.x - self.x).abs() + (rhs.y - self.y).abs()
-    // }
+    pub fn manhattan(&self, rhs: &Self) -> i64 {
+        (rhs.x - self.x).abs() + (rhs.y - self.y).abs()
+    }
+
+    /// Distance when diagonal steps are allowed, i.e. the number of king moves.
+    pub fn chebyshev(&self, rhs: &Self) -> i64 {
+        (rhs.x - self.x).abs().max((rhs.y - self.y).abs())
+    }
 
     pub fn offset(&self, o: &(i64, i64)) -> Point {
         Point {
@@ -85,20 +87,103 @@ impl Point {
     }
 
     pub fn r#move(self, dir: &Direction) -> Point {
-        match dir {
-            Direction::North => self.offset(&(0, -1)),
-            Direction::NorthEast => self.offset(&(1, -1)),
-            Direction::East => self.offset(&(1, 0)),
-            Direction::SouthEast => self.offset(&(1, 1)),
-            Direction::South => self.offset(&(0, 1)),
-            Direction::SouthWest => self.offset(&(-1, 1)),
-            Direction::West => self.offset(&(-1, 0)),
-            Direction::NorthWest => self.offset(&(-1, -1)),
-        }
+        self + dir.offset()
+    }
+
+    /// Neighbours in the given directions, paired with the direction they are in.
+    pub fn neighbours_in<'a>(
+        &self,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (Direction, Point)> + 'a {
+        let p = *self;
+        directions.iter().map(move |d| (*d, p.r#move(d)))
+    }
+
+    /// The four orthogonal neighbours, clockwise starting at north.
+    pub fn neighbours4(&self) -> impl Iterator<Item = Point> {
+        self.neighbours_in(&Direction::CARDINAL).map(|(_, p)| p)
+    }
+
+    /// All eight neighbours, clockwise starting at north.
+    pub fn neighbours8(&self) -> impl Iterator<Item = Point> {
+        self.neighbours_in(&Direction::ALL).map(|(_, p)| p)
+    }
+
+    /// The orthogonal neighbours that lie within `bounds`.
+    pub fn neighbours4_within<'a>(
+        &self,
+        bounds: &'a impl Bounds,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.neighbours4().filter(|p| bounds.contains(p))
+    }
+
+    /// All neighbours that lie within `bounds`.
+    pub fn neighbours8_within<'a>(
+        &self,
+        bounds: &'a impl Bounds,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.neighbours8().filter(|p| bounds.contains(p))
     }
 }
 
-impl std::ops::Add for Point {
+/// Anything that can tell whether a point lies inside it, such as a [`Grid`] or a [`Rect`].
+pub trait Bounds {
+    fn contains(&self, p: &Point) -> bool;
+}
+
+impl Ord for Point {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Point {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl FromStr for Point {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let coordinate = |token: &str| {
+            let token = token.trim();
+            token
+                .parse::<i64>()
+                .map_err(|_| Error::token(s, token, format!("'{}' is not a coordinate", token)))
+        };
+
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| Error::parse("expected a point like '3,4'").at_column(1))?;
+
+        Ok(Point {
+            x: coordinate(x)?,
+            y: coordinate(y)?,
+        })
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl From<Point> for (i64, i64) {
+    fn from(p: Point) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl ops::Add for Point {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -109,7 +194,53 @@ impl std::ops::Add for Point {
     }
 }
 
-impl std::ops::Neg for Point {
+impl ops::Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl ops::AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl ops::SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl ops::Mul<i64> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Point {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+/// Divides both coordinates, rounding towards zero like integer division does.
+impl ops::Div<i64> for Point {
+    type Output = Self;
+
+    fn div(self, rhs: i64) -> Self::Output {
+        Point {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
+
+impl ops::Neg for Point {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
@@ -119,3 +250,120 @@ impl std::ops::Neg for Point {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines() {
+        assert_eq!(split_lines("a\nb\n"), ["a", "b"]);
+        assert_eq!(split_lines("a\r\nb\r\n"), ["a", "b"]);
+        assert_eq!(split_lines("a\n\nb"), ["a", "", "b"]);
+        assert_eq!(split_lines("a\r\n\r\nb\r"), ["a", "", "b"]);
+    }
+
+    #[test]
+    fn reader() {
+        assert_eq!(
+            import_from("1 2\r\n3 4\r\n".as_bytes()).unwrap(),
+            ["1 2", "3 4"]
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let p = Point { x: 0, y: 0 };
+
+        assert_eq!(
+            p.neighbours4().collect::<Vec<_>>(),
+            [
+                Point { x: 0, y: -1 },
+                Point { x: 1, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: -1, y: 0 }
+            ]
+        );
+        assert_eq!(p.neighbours8().count(), 8);
+        assert!(p.neighbours8().all(|n| n != p));
+        assert_eq!(
+            p.neighbours_in(&Direction::ALL).nth(1),
+            Some((Direction::NorthEast, Point { x: 1, y: -1 }))
+        );
+    }
+
+    #[test]
+    fn neighbours_within() {
+        let grid = Grid::filled(3, 2, '.');
+
+        assert_eq!(
+            Point { x: 0, y: 0 }
+                .neighbours4_within(&grid)
+                .collect::<Vec<_>>(),
+            [Point { x: 1, y: 0 }, Point { x: 0, y: 1 }]
+        );
+        assert_eq!(Point { x: 1, y: 1 }.neighbours8_within(&grid).count(), 5);
+        assert_eq!(Point { x: 5, y: 5 }.neighbours8_within(&grid).count(), 0);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point { x: 3, y: -4 };
+
+        assert_eq!(p - Point { x: 1, y: 1 }, Point { x: 2, y: -5 });
+        assert_eq!(p * 2, Point { x: 6, y: -8 });
+        assert_eq!(p / 2, Point { x: 1, y: -2 });
+        assert_eq!(-p, Point { x: -3, y: 4 });
+
+        p += Point { x: 1, y: 1 };
+        assert_eq!(p, Point { x: 4, y: -3 });
+        p -= Point { x: 4, y: -3 };
+        assert_eq!(p, Point { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn distance() {
+        let (a, b) = (Point { x: 1, y: 2 }, Point { x: -2, y: 8 });
+
+        assert_eq!(a.manhattan(&b), 9);
+        assert_eq!(b.manhattan(&a), 9);
+        assert_eq!(a.chebyshev(&b), 6);
+        assert_eq!(a.chebyshev(&a), 0);
+    }
+
+    #[test]
+    fn order() {
+        let mut points = vec![
+            Point { x: 0, y: 1 },
+            Point { x: 2, y: 0 },
+            Point { x: 1, y: 1 },
+            Point { x: 0, y: 0 },
+        ];
+        points.sort();
+
+        assert_eq!(
+            points,
+            [
+                Point { x: 0, y: 0 },
+                Point { x: 2, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 1, y: 1 }
+            ]
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let p = Point { x: -3, y: 12 };
+
+        assert_eq!(p.to_string(), "-3,12");
+        assert_eq!("-3,12".parse::<Point>().unwrap(), p);
+        assert_eq!(" -3, 12".parse::<Point>().unwrap(), p);
+        assert_eq!(Point::from((-3, 12)), p);
+        assert_eq!(<(i64, i64)>::from(p), (-3, 12));
+
+        let e = "3,x".parse::<Point>().err().unwrap();
+        assert_eq!(e.to_string(), "3: 'x' is not a coordinate");
+        assert!("3".parse::<Point>().is_err());
+    }
+}
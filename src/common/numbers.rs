@@ -0,0 +1,167 @@
+use crate::error::{Error, Result};
+
+/// Every signed integer in `line`, whatever separates them. A `-` directly in front of digits
+/// makes the number negative.
+pub fn integers(line: &str) -> Result<Vec<i64>> {
+    let mut numbers = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        let start = if rest[..start].ends_with('-') {
+            start - 1
+        } else {
+            start
+        };
+        let end = rest[start + 1..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(rest.len(), |end| start + 1 + end);
+        let token = &rest[start..end];
+
+        numbers.push(
+            token
+                .parse()
+                .map_err(|_| Error::token(line, token, format!("'{}' is out of range", token)))?,
+        );
+        rest = &rest[end..];
+    }
+
+    Ok(numbers)
+}
+
+/// The fields of `line` between `separators`. Whitespace may repeat, so empty fields next to a
+/// whitespace separator are left out, as are empty fields at the ends of the line when there is
+/// a whitespace separator. `"3   4"` split on `' '` gives two fields, `"3,,4"` split on `','`
+/// gives three.
+fn fields<'a>(line: &'a str, separators: &'a [char]) -> impl Iterator<Item = &'a str> {
+    let whitespace = move |c: Option<char>| {
+        c.map_or(
+            separators.iter().any(|s| s.is_whitespace()),
+            char::is_whitespace,
+        )
+    };
+    let mut start = 0;
+
+    line.split(separators).filter(move |field| {
+        let before = line[..start].chars().next_back();
+        start += field.len();
+        let after = line[start..].chars().next();
+        start += after.map_or(0, char::len_utf8);
+
+        !field.is_empty() || !(whitespace(before) || whitespace(after))
+    })
+}
+
+/// Splits `line` on any of the `separators` and parses every field as an integer, runs of
+/// whitespace count as one separator. A field that is not an integer is reported as not being a
+/// `what`, e.g. "'x' is not a page".
+pub fn split_integers(line: &str, separators: &[char], what: &str) -> Result<Vec<i64>> {
+    fields(line, separators)
+        .map(|field| {
+            field
+                .parse()
+                .map_err(|_| Error::token(line, field, format!("'{}' is not a {}", field, what)))
+        })
+        .collect()
+}
+
+/// Like [`split_integers`], but there must be exactly `N` fields.
+pub fn split_tuple<const N: usize>(
+    line: &str,
+    separators: &[char],
+    what: &str,
+) -> Result<[i64; N]> {
+    let numbers = split_integers(line, separators, what)?;
+    let found = numbers.len();
+
+    numbers.try_into().map_err(|_| {
+        let message = format!("expected {} {}s but found {}", N, what, found);
+        let extra = fields(line, separators).nth(N);
+        match extra {
+            Some(field) => Error::token(line, field, message),
+            None => Error::parse(message).at_column(line.chars().count() + 1),
+        }
+    })
+}
+
+/// The [`integers`] of every line, errors get the line number they occurred on.
+pub fn parse_integers(input: &[String]) -> Result<Vec<Vec<i64>>> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, line)| integers(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract() {
+        assert_eq!(integers("p=0,-4 v=3,-3 x-1").unwrap(), [0, -4, 3, -3, -1]);
+        assert_eq!(integers("Button A: X+94, Y+34").unwrap(), [94, 34]);
+        assert_eq!(integers("no numbers - here").unwrap(), []);
+
+        let e = integers("1 99999999999999999999").unwrap_err();
+        assert_eq!(e.to_string(), "3: '99999999999999999999' is out of range");
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(
+            split_integers("7 6  4", &[' '], "level").unwrap(),
+            [7, 6, 4]
+        );
+        assert_eq!(split_integers(" 1  2 ", &[' '], "n").unwrap(), [1, 2]);
+        assert_eq!(split_integers("", &[' '], "n").unwrap(), []);
+        assert_eq!(split_integers("1, 2", &[',', ' '], "n").unwrap(), [1, 2]);
+        assert_eq!(
+            split_integers("1,,2", &[','], "page")
+                .unwrap_err()
+                .to_string(),
+            "3: '' is not a page"
+        );
+        assert_eq!(
+            split_integers("", &[','], "page").unwrap_err().to_string(),
+            "1: '' is not a page"
+        );
+        assert_eq!(
+            split_integers("75,x7", &[','], "page")
+                .unwrap_err()
+                .to_string(),
+            "4: 'x7' is not a page"
+        );
+
+        assert_eq!(split_tuple::<2>("47|53", &['|'], "page").unwrap(), [47, 53]);
+        assert_eq!(
+            split_tuple::<3>("1 -2,3", &[' ', ','], "n").unwrap(),
+            [1, -2, 3]
+        );
+        assert_eq!(
+            split_tuple::<2>("47", &['|'], "page")
+                .unwrap_err()
+                .to_string(),
+            "3: expected 2 pages but found 1"
+        );
+        assert_eq!(
+            split_tuple::<2>("1|2|3", &['|'], "page")
+                .unwrap_err()
+                .to_string(),
+            "5: expected 2 pages but found 3"
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let input = ["1 2", "", "-3"].map(|s| s.to_string());
+
+        assert_eq!(
+            parse_integers(&input).unwrap(),
+            [vec![1, 2], vec![], vec![-3]]
+        );
+
+        let e =
+            parse_integers(&["1".to_string(), "x 12345678901234567890".to_string()]).unwrap_err();
+        assert_eq!((e.line, e.column), (Some(2), Some(3)));
+    }
+}
@@ -0,0 +1,152 @@
+use super::{Bounds, Point};
+
+/// Axis-aligned rectangle spanning `min` to `max`, both corners included. It always contains at
+/// least one point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect {
+    min: Point,
+    max: Point,
+}
+
+impl Rect {
+    /// The rectangle with `a` and `b` as opposite corners, in any order.
+    pub fn new(a: Point, b: Point) -> Self {
+        Rect {
+            min: Point {
+                x: a.x.min(b.x),
+                y: a.y.min(b.y),
+            },
+            max: Point {
+                x: a.x.max(b.x),
+                y: a.y.max(b.y),
+            },
+        }
+    }
+
+    /// The corner with the smallest coordinates.
+    pub fn min(&self) -> Point {
+        self.min
+    }
+
+    /// The corner with the largest coordinates.
+    pub fn max(&self) -> Point {
+        self.max
+    }
+
+    /// The smallest rectangle containing all `points`, `None` when there are none.
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        Some(points.fold(Rect::new(first, first), |rect, p| rect.including(p)))
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// All points in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Rect { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point { x, y }))
+    }
+
+    /// The smallest rectangle containing this one and `p`.
+    pub fn including(&self, p: &Point) -> Rect {
+        self.union(&Rect::new(*p, *p))
+    }
+
+    /// The overlapping part, `None` when the rectangles are disjoint.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let min = Point {
+            x: self.min.x.max(other.min.x),
+            y: self.min.y.max(other.min.y),
+        };
+        let max = Point {
+            x: self.max.x.min(other.max.x),
+            y: self.max.y.min(other.max.y),
+        };
+        (min.x <= max.x && min.y <= max.y).then_some(Rect { min, max })
+    }
+
+    /// The smallest rectangle containing both.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: Point {
+                x: self.min.x.min(other.min.x),
+                y: self.min.y.min(other.min.y),
+            },
+            max: Point {
+                x: self.max.x.max(other.max.x),
+                y: self.max.y.max(other.max.y),
+            },
+        }
+    }
+
+    /// The point inside the rectangle closest to `p`.
+    pub fn clamp(&self, p: &Point) -> Point {
+        Point {
+            x: p.x.clamp(self.min.x, self.max.x),
+            y: p.y.clamp(self.min.y, self.max.y),
+        }
+    }
+}
+
+impl Bounds for Rect {
+    fn contains(&self, p: &Point) -> bool {
+        Rect::contains(self, p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    #[test]
+    fn from_points() {
+        let rect = Rect::from_points(&[p(3, -1), p(-2, 4), p(0, 0)]).unwrap();
+
+        assert_eq!(rect, Rect::new(p(3, 4), p(-2, -1)));
+        assert_eq!((rect.min(), rect.max()), (p(-2, -1), p(3, 4)));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (6, 6, 36));
+        assert!(rect.contains(&p(-2, 4)) && !rect.contains(&p(4, 0)));
+        assert_eq!(Rect::from_points(&[]), None);
+        assert_eq!(Rect::from_points(&[p(1, 1)]).unwrap().area(), 1);
+    }
+
+    #[test]
+    fn points() {
+        assert_eq!(
+            Rect::new(p(1, 1), p(2, 2)).points().collect::<Vec<_>>(),
+            [p(1, 1), p(2, 1), p(1, 2), p(2, 2)]
+        );
+    }
+
+    #[test]
+    fn combine() {
+        let a = Rect::new(p(0, 0), p(4, 4));
+        let b = Rect::new(p(3, 2), p(6, 8));
+
+        assert_eq!(a.intersection(&b), Some(Rect::new(p(3, 2), p(4, 4))));
+        assert_eq!(a.union(&b), Rect::new(p(0, 0), p(6, 8)));
+        assert_eq!(a.intersection(&Rect::new(p(5, 0), p(6, 1))), None);
+        assert_eq!(a.including(&p(-1, 2)), Rect::new(p(-1, 0), p(4, 4)));
+        assert_eq!(a.clamp(&p(-3, 7)), p(0, 4));
+        assert_eq!(a.clamp(&p(2, 3)), p(2, 3));
+    }
+}
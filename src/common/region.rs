@@ -0,0 +1,224 @@
+use std::collections::BTreeSet;
+
+use super::{Bounds, Direction, Grid, Point, Rect, Rotation};
+
+/// Orthogonally connected set of positions, such as a blob of equal cells in a [`Grid`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    points: BTreeSet<Point>,
+}
+
+impl Region {
+    /// The positions in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = &Point> {
+        self.points.iter()
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        self.points.contains(p)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Number of positions.
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Number of unit edges between a position in the region and one outside it.
+    pub fn perimeter(&self) -> usize {
+        self.points
+            .iter()
+            .map(|p| p.neighbours4().filter(|n| !self.contains(n)).count())
+            .sum()
+    }
+
+    /// Number of straight fences around the region, holes included. A polygon has as many sides
+    /// as corners, so this counts the corners of every position.
+    pub fn sides(&self) -> usize {
+        self.points
+            .iter()
+            .map(|p| {
+                Direction::CARDINAL
+                    .iter()
+                    .filter(|d| {
+                        let a = self.contains(&p.r#move(d));
+                        let b = self.contains(&p.r#move(&d.rotate90(&Rotation::CW)));
+                        let between = self.contains(&p.r#move(&d.rotate(&Rotation::CW)));
+                        (!a && !b) || (a && b && !between)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The smallest rectangle containing the region, `None` when it is empty.
+    pub fn bounds(&self) -> Option<Rect> {
+        Rect::from_points(&self.points)
+    }
+}
+
+impl Bounds for Region {
+    fn contains(&self, p: &Point) -> bool {
+        Region::contains(self, p)
+    }
+}
+
+/// Every region of a grid, with the index of the region each cell belongs to.
+#[derive(Clone, Debug)]
+pub struct Labels {
+    /// Per cell the index into `regions`, `None` for cells that are in no region.
+    pub labels: Grid<Option<usize>>,
+    /// The regions in the row-major order of their first cell.
+    pub regions: Vec<Region>,
+}
+
+impl Labels {
+    /// The region `p` belongs to.
+    pub fn region_at(&self, p: &Point) -> Option<&Region> {
+        self.labels
+            .get(p)
+            .copied()
+            .flatten()
+            .map(|i| &self.regions[i])
+    }
+}
+
+impl<T> Grid<T> {
+    /// The region of cells connected to `seed` for which `include` holds, empty when it does
+    /// not hold for `seed` itself.
+    pub fn flood_fill(&self, seed: &Point, include: impl Fn(&T) -> bool) -> Region {
+        self.fill(seed, |_, p| include(&self[p]))
+    }
+
+    /// Labels the regions of connected cells for which `include` holds.
+    pub fn label_by(&self, include: impl Fn(&T) -> bool) -> Labels {
+        self.label_with(|_, p| include(&self[p]))
+    }
+
+    /// Labels the regions of connected cells with equal values, every cell is in one.
+    pub fn label(&self) -> Labels
+    where
+        T: PartialEq,
+    {
+        self.label_with(|seed, p| self[seed] == self[p])
+    }
+
+    /// The positions connected to `seed` through neighbours for which `connected(seed, p)`
+    /// holds, empty when it does not hold for `seed` itself.
+    fn fill(&self, seed: &Point, connected: impl Fn(&Point, &Point) -> bool) -> Region {
+        let mut points = BTreeSet::new();
+        if !self.contains(seed) || !connected(seed, seed) {
+            return Region { points };
+        }
+
+        let mut todo = vec![*seed];
+        points.insert(*seed);
+        while let Some(p) = todo.pop() {
+            for n in p.neighbours4_within(self) {
+                if connected(seed, &n) && points.insert(n) {
+                    todo.push(n);
+                }
+            }
+        }
+
+        Region { points }
+    }
+
+    fn label_with(&self, connected: impl Fn(&Point, &Point) -> bool) -> Labels {
+        let mut labels = self.map(|_| None);
+        let mut regions = Vec::new();
+
+        for p in self.points() {
+            if labels[&p].is_some() {
+                continue;
+            }
+            let region = self.fill(&p, &connected);
+            if !region.is_empty() {
+                region
+                    .points()
+                    .for_each(|q| labels[q] = Some(regions.len()));
+                regions.push(region);
+            }
+        }
+
+        Labels { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn garden(lines: &[&str]) -> Grid<char> {
+        let lines = lines.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        Grid::try_from(lines.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn statistics() {
+        let garden = garden(&["AAAA", "BBCD", "BBCC", "EEEC"]);
+        let labels = garden.label();
+
+        let stats = labels
+            .regions
+            .iter()
+            .map(|region| {
+                let plant = garden[region.points().next().unwrap()];
+                (plant, region.area(), region.perimeter(), region.sides())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            stats,
+            [
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4)
+            ]
+        );
+        assert_eq!(
+            labels.region_at(&Point { x: 3, y: 3 }).unwrap().bounds(),
+            Some(Rect::new(Point { x: 2, y: 1 }, Point { x: 3, y: 3 }))
+        );
+    }
+
+    #[test]
+    fn holes() {
+        let garden = garden(&["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"]);
+        let labels = garden.label();
+
+        assert_eq!(labels.regions.len(), 5);
+        assert_eq!(labels.regions[0].area(), 21);
+        assert_eq!(labels.regions[0].perimeter(), 36);
+        assert_eq!(labels.regions[0].sides(), 20);
+    }
+
+    #[test]
+    fn fill() {
+        let garden = garden(&["#..#", "#.##", "##.."]);
+
+        assert_eq!(
+            garden
+                .flood_fill(&Point { x: 1, y: 0 }, |&c| c == '.')
+                .area(),
+            3
+        );
+        assert!(garden
+            .flood_fill(&Point { x: 0, y: 0 }, |&c| c == '.')
+            .is_empty());
+        assert!(garden
+            .flood_fill(&Point { x: 9, y: 0 }, |&c| c == '.')
+            .is_empty());
+
+        let labels = garden.label_by(|&c| c == '#');
+        assert_eq!(labels.regions.len(), 2);
+        assert_eq!(labels.labels[&Point { x: 1, y: 0 }], None);
+        assert_eq!(labels.labels[&Point { x: 1, y: 2 }], Some(0));
+        assert_eq!(labels.labels[&Point { x: 2, y: 1 }], Some(1));
+    }
+}
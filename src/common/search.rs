@@ -0,0 +1,267 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use super::{Bounds, Point};
+
+/// Result of a search from a single start: the distance to every reached point and, for each of
+/// them, all neighbours it can be reached from on a shortest path.
+#[derive(Clone, Debug)]
+pub struct Paths {
+    start: Point,
+    distances: HashMap<Point, u64>,
+    previous: HashMap<Point, Vec<Point>>,
+}
+
+impl Paths {
+    fn new(start: Point) -> Self {
+        Paths {
+            start,
+            distances: HashMap::from([(start, 0)]),
+            previous: HashMap::new(),
+        }
+    }
+
+    /// Records reaching `to` from `from` at `distance`, true when that is shorter than before.
+    fn relax(&mut self, from: Point, to: Point, distance: u64) -> bool {
+        match self.distances.get(&to) {
+            _ if to == self.start => false,
+            Some(&known) if known < distance => false,
+            Some(&known) if known == distance => {
+                self.previous.entry(to).or_default().push(from);
+                false
+            }
+            _ => {
+                self.distances.insert(to, distance);
+                self.previous.insert(to, vec![from]);
+                true
+            }
+        }
+    }
+
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    /// Length of the shortest path to `p`, `None` when it was not reached.
+    pub fn distance(&self, p: &Point) -> Option<u64> {
+        self.distances.get(p).copied()
+    }
+
+    /// Every reached point with its distance.
+    pub fn distances(&self) -> &HashMap<Point, u64> {
+        &self.distances
+    }
+
+    /// One shortest path from the start to `p`, both included.
+    pub fn path_to(&self, p: &Point) -> Option<Vec<Point>> {
+        self.distances.get(p)?;
+
+        let mut path = vec![*p];
+        while let Some(previous) = self.previous.get(path.last().unwrap()) {
+            path.push(previous[0]);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All shortest paths from the start to `p`. Their number can grow exponentially, see
+    /// [`Paths::on_shortest_paths`] when only the points matter.
+    pub fn all_paths_to(&self, p: &Point) -> Vec<Vec<Point>> {
+        if !self.distances.contains_key(p) {
+            return Vec::new();
+        }
+
+        match self.previous.get(p) {
+            None => vec![vec![*p]],
+            Some(previous) => previous
+                .iter()
+                .flat_map(|q| self.all_paths_to(q))
+                .map(|mut path| {
+                    path.push(*p);
+                    path
+                })
+                .collect(),
+        }
+    }
+
+    /// Every point that lies on at least one shortest path to `p`.
+    pub fn on_shortest_paths(&self, p: &Point) -> HashSet<Point> {
+        let mut seen = HashSet::new();
+        let mut todo = self
+            .distances
+            .contains_key(p)
+            .then_some(*p)
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        while let Some(q) = todo.pop() {
+            if seen.insert(q) {
+                todo.extend(self.previous.get(&q).into_iter().flatten());
+            }
+        }
+
+        seen
+    }
+}
+
+/// Breadth-first search from `start` over the orthogonal neighbours within `bounds` for which
+/// `passable` holds, every step costs 1.
+pub fn bfs(bounds: &impl Bounds, start: Point, passable: impl Fn(&Point) -> bool) -> Paths {
+    let mut paths = Paths::new(start);
+    let mut todo = VecDeque::from([start]);
+
+    while let Some(p) = todo.pop_front() {
+        let distance = paths.distances[&p] + 1;
+        for next in p.neighbours4_within(bounds).filter(&passable) {
+            if paths.relax(p, next, distance) {
+                todo.push_back(next);
+            }
+        }
+    }
+
+    paths
+}
+
+/// Shortest paths from `start` over the orthogonal neighbours within `bounds`. `cost` gives the
+/// cost of stepping from the first point to the second, `None` when that step is not possible.
+/// Every step must cost at least 1, zero-cost steps panic as neighbours could otherwise be on
+/// each other's shortest paths.
+pub fn dijkstra(
+    bounds: &impl Bounds,
+    start: Point,
+    cost: impl Fn(&Point, &Point) -> Option<u64>,
+) -> Paths {
+    let mut paths = Paths::new(start);
+    let mut todo = BinaryHeap::from([Reverse((0, start))]);
+
+    while let Some(Reverse((distance, p))) = todo.pop() {
+        if distance > paths.distances[&p] {
+            continue;
+        }
+        for next in p.neighbours4_within(bounds) {
+            if let Some(step) = positive(cost(&p, &next)) {
+                if paths.relax(p, next, distance + step) {
+                    todo.push(Reverse((distance + step, next)));
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+/// Like [`dijkstra`], also in rejecting zero-cost steps, but stops once every shortest path to
+/// `goal` is known. The `heuristic`
+/// estimates the remaining cost to `goal` and must never overestimate it, e.g.
+/// [`Point::manhattan`] when every step costs at least 1.
+pub fn astar(
+    bounds: &impl Bounds,
+    start: Point,
+    goal: Point,
+    cost: impl Fn(&Point, &Point) -> Option<u64>,
+    heuristic: impl Fn(&Point) -> u64,
+) -> Paths {
+    let mut paths = Paths::new(start);
+    let mut todo = BinaryHeap::from([Reverse((heuristic(&start), 0, start))]);
+
+    while let Some(Reverse((estimate, distance, p))) = todo.pop() {
+        if paths.distance(&goal).is_some_and(|best| estimate > best) {
+            break;
+        }
+        if distance > paths.distances[&p] {
+            continue;
+        }
+        for next in p.neighbours4_within(bounds) {
+            if let Some(step) = positive(cost(&p, &next)) {
+                if paths.relax(p, next, distance + step) {
+                    todo.push(Reverse((
+                        distance + step + heuristic(&next),
+                        distance + step,
+                        next,
+                    )));
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+/// `cost`, which must not be zero, see [`dijkstra`].
+fn positive(cost: Option<u64>) -> Option<u64> {
+    assert_ne!(cost, Some(0), "steps must cost at least 1");
+    cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Grid;
+
+    fn maze() -> Grid<char> {
+        let lines = ["S..#", ".#..", "...E", "#.#."].map(|s| s.to_string());
+        Grid::try_from(lines.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn breadth_first() {
+        let maze = maze();
+        let (start, end) = (
+            maze.position(|&c| c == 'S').unwrap(),
+            maze.position(|&c| c == 'E').unwrap(),
+        );
+        let paths = bfs(&maze, start, |p| maze[p] != '#');
+
+        assert_eq!(paths.distance(&end), Some(5));
+        assert_eq!(paths.distance(&Point { x: 3, y: 0 }), None);
+        assert_eq!(paths.path_to(&end).unwrap().len(), 6);
+        assert_eq!(paths.path_to(&start), Some(vec![start]));
+
+        let all = paths.all_paths_to(&end);
+        assert_eq!(all.len(), 3);
+        assert!(all.iter().all(|path| path[0] == start && path[5] == end));
+        assert_eq!(paths.on_shortest_paths(&end).len(), 10);
+    }
+
+    #[test]
+    fn weighted() {
+        let maze = maze();
+        let (start, end) = (Point { x: 0, y: 0 }, Point { x: 3, y: 2 });
+        // Entering the third row before the last column costs 10, so only the path through the
+        // top right remains shortest.
+        let cost = |_: &Point, to: &Point| match maze[to] {
+            '#' => None,
+            _ if to.y == 2 && to.x < 3 => Some(10),
+            _ => Some(1),
+        };
+
+        let paths = dijkstra(&maze, start, cost);
+        assert_eq!(paths.distance(&end), Some(5));
+        assert_eq!(
+            paths.path_to(&end).unwrap(),
+            [(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (3, 2)].map(Point::from)
+        );
+        assert_eq!(paths.distance(&Point { x: 3, y: 3 }), Some(6));
+
+        let paths = astar(&maze, start, end, cost, |p| p.manhattan(&end) as u64);
+        assert_eq!(paths.distance(&end), Some(5));
+        assert_eq!(paths.all_paths_to(&end).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "steps must cost at least 1")]
+    fn zero_cost() {
+        let grid = Grid::new(3, 2, vec![0; 6]);
+
+        dijkstra(&grid, Point { x: 0, y: 0 }, |_, _| Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "steps must cost at least 1")]
+    fn zero_cost_astar() {
+        let grid = Grid::new(3, 2, vec![0; 6]);
+        let goal = Point { x: 2, y: 1 };
+
+        astar(&grid, Point { x: 0, y: 0 }, goal, |_, _| Some(0), |_| 0);
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use super::{Point, Rect};
+
+/// Unbounded grid that only stores the cells that were set, every other cell reads as `default`.
+/// The bounding box of the set cells is kept up to date as cells are added.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<Rect>,
+    default: T,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+            default,
+        }
+    }
+
+    /// Number of cells that were set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest rectangle containing every set cell, `None` when nothing is set.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    pub fn is_set(&self, p: &Point) -> bool {
+        self.cells.contains_key(p)
+    }
+
+    /// The cell at `p`, the default when it was never set.
+    pub fn get(&self, p: &Point) -> &T {
+        self.cells.get(p).unwrap_or(&self.default)
+    }
+
+    /// Sets the cell at `p`, returning the value it had if it was set.
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.include(&p);
+        self.cells.insert(p, value)
+    }
+
+    /// Unsets the cell at `p`. The bounds only shrink when `p` was on their edge.
+    pub fn remove(&mut self, p: &Point) -> Option<T> {
+        let removed = self.cells.remove(p)?;
+
+        if let Some(bounds) = self.bounds {
+            let (min, max) = (bounds.min(), bounds.max());
+            if p.x == min.x || p.x == max.x || p.y == min.y || p.y == max.y {
+                self.bounds = Rect::from_points(self.cells.keys());
+            }
+        }
+
+        Some(removed)
+    }
+
+    /// The set cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
+        self.cells.iter()
+    }
+
+    /// The bounds with `padding` extra cells on every side, one line per row, each cell drawn
+    /// by `f`. Empty when nothing is set.
+    pub fn render(&self, padding: i64, f: impl Fn(&T) -> char) -> String {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+        let (min, max) = (bounds.min(), bounds.max());
+
+        (min.y - padding..=max.y + padding)
+            .map(|y| {
+                (min.x - padding..=max.x + padding)
+                    .map(|x| f(self.get(&Point { x, y })))
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn include(&mut self, p: &Point) {
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => bounds.including(p),
+            None => Rect::new(*p, *p),
+        });
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// The cell at `p`, which is set to the default first when it was never set.
+    pub fn get_mut(&mut self, p: &Point) -> &mut T {
+        self.include(p);
+        self.cells.entry(*p).or_insert_with(|| self.default.clone())
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(p, value)| {
+            self.insert(p, value);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grows() {
+        let mut grid = SparseGrid::new('.');
+
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render(1, |c| *c), "");
+
+        grid.insert(Point { x: 0, y: 0 }, '#');
+        grid.extend([(Point { x: -2, y: 1 }, '#')]);
+        *grid.get_mut(&Point { x: 1, y: -1 }) = 'o';
+
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.get(&Point { x: 5, y: 5 }), &'.');
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect::new(Point { x: -2, y: -1 }, Point { x: 1, y: 1 }))
+        );
+        assert_eq!(grid.render(0, |c| *c), "...o\n..#.\n#...\n");
+        assert_eq!(
+            grid.render(1, |c| *c),
+            "......\n....o.\n...#..\n.#....\n......\n"
+        );
+    }
+
+    #[test]
+    fn shrinks() {
+        let mut grid = SparseGrid::new(0);
+        grid.extend([(Point { x: 0, y: 0 }, 1), (Point { x: 3, y: 3 }, 2)]);
+
+        assert_eq!(grid.remove(&Point { x: 3, y: 3 }), Some(2));
+        assert_eq!(grid.remove(&Point { x: 3, y: 3 }), None);
+        assert_eq!(grid.get(&Point { x: 3, y: 3 }), &0);
+        assert_eq!(grid.bounds().map(|rect| rect.area()), Some(1));
+
+        grid.remove(&Point { x: 0, y: 0 });
+        assert_eq!(grid.bounds(), None);
+    }
+}
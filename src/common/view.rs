@@ -0,0 +1,122 @@
+use std::ops::Index;
+
+use super::{Bounds, Grid, Point, Rect};
+
+/// Borrowed rectangular part of a [`Grid`]. Positions are relative to the view, (0, 0) is its
+/// top left cell.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rect: Rect,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub(super) fn new(grid: &'a Grid<T>, rect: &Rect) -> Option<Self> {
+        let rect = grid.bounds()?.intersection(rect)?;
+        Some(GridView { grid, rect })
+    }
+
+    pub fn width(&self) -> usize {
+        self.rect.width() as usize
+    }
+
+    pub fn height(&self) -> usize {
+        self.rect.height() as usize
+    }
+
+    /// The part of the grid this view covers, in grid positions.
+    pub fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        (0..self.width() as i64).contains(&p.x) && (0..self.height() as i64).contains(&p.y)
+    }
+
+    /// The cell at `p` relative to the view, `None` outside the view.
+    pub fn get(&self, p: &Point) -> Option<&'a T> {
+        self.contains(p)
+            .then(|| self.grid.get(&(self.rect.min() + *p)))
+            .flatten()
+    }
+
+    /// All positions relative to the view in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let min = self.rect.min();
+        self.rect.points().map(move |p| p - min)
+    }
+
+    /// All cells with their position relative to the view in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &'a T)> {
+        let view = *self;
+        self.points().map(move |p| (p, view.get(&p).unwrap()))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let (grid, min, max) = (self.grid, self.rect.min(), self.rect.max());
+        (min.y..=max.y)
+            .map(move |y| &grid.row(y as usize).unwrap()[min.x as usize..=max.x as usize])
+    }
+
+    /// Text with one line per row, each cell drawn by `f`.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&f).chain(['\n']).collect::<String>())
+            .collect()
+    }
+
+    /// Copies the cells into a grid of their own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width(), self.height(), |p| self[&p].clone())
+    }
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T> Index<&Point> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, p: &Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{:?} is outside the view", p))
+    }
+}
+
+impl<T> Bounds for GridView<'_, T> {
+    fn contains(&self, p: &Point) -> bool {
+        GridView::contains(self, p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn view() {
+        let grid = Grid::from_fn(4, 3, |p| (b'a' + (p.y * 4 + p.x) as u8) as char);
+        let view = grid
+            .view(&Rect::new(Point { x: 1, y: 1 }, Point { x: 5, y: 5 }))
+            .unwrap();
+
+        assert_eq!((view.width(), view.height()), (3, 2));
+        assert_eq!(view.render(|c| *c), "fgh\njkl\n");
+        assert_eq!(view.get(&Point { x: 0, y: 1 }), Some(&'j'));
+        assert_eq!(view.get(&Point { x: 3, y: 0 }), None);
+        assert_eq!(view.iter().nth(4), Some((Point { x: 1, y: 1 }, &'k')));
+        assert_eq!(view.to_grid().to_string(), "fgh\njkl\n");
+
+        assert!(grid
+            .view(&Rect::new(Point { x: 4, y: 0 }, Point { x: 6, y: 6 }))
+            .is_none());
+    }
+}
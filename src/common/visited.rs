@@ -0,0 +1,208 @@
+use super::{Direction, Point};
+
+/// Set of positions in a `width` by `height` area with its origin at (0, 0), one bit per
+/// position. Positions outside the area are never in the set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Visited {
+    width: usize,
+    height: usize,
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl Visited {
+    pub fn new(width: usize, height: usize) -> Self {
+        Visited {
+            width,
+            height,
+            bits: vec![0; (width * height).div_ceil(64)],
+            len: 0,
+        }
+    }
+
+    fn index_of(&self, p: &Point) -> Option<usize> {
+        ((0..self.width as i64).contains(&p.x) && (0..self.height as i64).contains(&p.y))
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    /// Adds `p`, returns true when it was not in the set yet. Positions outside the area are
+    /// ignored and give false.
+    pub fn insert(&mut self, p: &Point) -> bool {
+        let Some(i) = self.index_of(p) else {
+            return false;
+        };
+        let (word, bit) = (&mut self.bits[i / 64], 1 << (i % 64));
+        let added = *word & bit == 0;
+        *word |= bit;
+        self.len += added as usize;
+        added
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        self.index_of(p)
+            .is_some_and(|i| self.bits[i / 64] & (1 << (i % 64)) != 0)
+    }
+
+    /// Number of positions in the set, kept up to date so this does not scan.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+        self.len = 0;
+    }
+
+    /// The positions in the set in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        let width = self.width;
+        self.bits
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| **word != 0)
+            .flat_map(|(w, &word)| {
+                (0..64)
+                    .filter(move |bit| word & (1 << bit) != 0)
+                    .map(move |bit| w * 64 + bit)
+            })
+            .map(move |i| Point {
+                x: (i % width) as i64,
+                y: (i / width) as i64,
+            })
+    }
+}
+
+/// Like [`Visited`], but remembers in which of the eight directions every position was
+/// visited, one bit per direction. Useful to detect walkers that repeat themselves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VisitedDirections {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+    len: usize,
+}
+
+impl VisitedDirections {
+    pub fn new(width: usize, height: usize) -> Self {
+        VisitedDirections {
+            width,
+            height,
+            cells: vec![0; width * height],
+            len: 0,
+        }
+    }
+
+    fn index_of(&self, p: &Point) -> Option<usize> {
+        ((0..self.width as i64).contains(&p.x) && (0..self.height as i64).contains(&p.y))
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    /// Adds `p` facing `direction`, returns true when that pair was not in the set yet.
+    /// Positions outside the area are ignored and give false.
+    pub fn insert(&mut self, p: &Point, direction: impl Into<Direction>) -> bool {
+        let bit = 1 << direction.into() as u8;
+        let Some(i) = self.index_of(p) else {
+            return false;
+        };
+        let added = self.cells[i] & bit == 0;
+        self.cells[i] |= bit;
+        self.len += added as usize;
+        added
+    }
+
+    pub fn contains(&self, p: &Point, direction: impl Into<Direction>) -> bool {
+        let bit = 1 << direction.into() as u8;
+        self.index_of(p).is_some_and(|i| self.cells[i] & bit != 0)
+    }
+
+    /// True when `p` was visited in any direction.
+    pub fn contains_point(&self, p: &Point) -> bool {
+        self.index_of(p).is_some_and(|i| self.cells[i] != 0)
+    }
+
+    /// The directions `p` was visited in, clockwise starting at north.
+    pub fn directions(&self, p: &Point) -> impl Iterator<Item = Direction> {
+        let cell = self.index_of(p).map_or(0, |i| self.cells[i]);
+        Direction::all().filter(move |d| cell & (1 << *d as u8) != 0)
+    }
+
+    /// Number of position and direction pairs in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(0);
+        self.len = 0;
+    }
+
+    /// The positions visited in any direction, in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| **cell != 0)
+            .map(move |(i, _)| Point {
+                x: (i % width) as i64,
+                y: (i / width) as i64,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Cardinal;
+
+    #[test]
+    fn visited() {
+        let mut visited = Visited::new(10, 10);
+
+        assert!(visited.insert(&Point { x: 9, y: 6 }));
+        assert!(visited.insert(&Point { x: 3, y: 0 }));
+        assert!(!visited.insert(&Point { x: 3, y: 0 }));
+        assert!(!visited.insert(&Point { x: 10, y: 0 }));
+        assert!(!visited.insert(&Point { x: -1, y: 0 }));
+
+        assert_eq!(visited.len(), 2);
+        assert!(visited.contains(&Point { x: 9, y: 6 }));
+        assert!(!visited.contains(&Point { x: 6, y: 9 }));
+        assert_eq!(
+            visited.iter().collect::<Vec<_>>(),
+            [Point { x: 3, y: 0 }, Point { x: 9, y: 6 }]
+        );
+
+        visited.clear();
+        assert!(visited.is_empty() && visited.iter().next().is_none());
+    }
+
+    #[test]
+    fn directions() {
+        let mut visited = VisitedDirections::new(3, 3);
+        let p = Point { x: 1, y: 2 };
+
+        assert!(visited.insert(&p, Direction::West));
+        assert!(visited.insert(&p, Cardinal::North));
+        assert!(!visited.insert(&p, Direction::North));
+        assert!(visited.insert(&Point { x: 0, y: 0 }, Direction::SouthEast));
+
+        assert_eq!(visited.len(), 3);
+        assert!(visited.contains(&p, Cardinal::West));
+        assert!(!visited.contains(&p, Direction::East));
+        assert!(visited.contains_point(&p));
+        assert_eq!(
+            visited.directions(&p).collect::<Vec<_>>(),
+            [Direction::North, Direction::West]
+        );
+        assert_eq!(visited.points().count(), 2);
+    }
+}
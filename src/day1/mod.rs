@@ -1,20 +1,31 @@
-use crate::common::import;
+use std::str::FromStr;
 
-fn parse_into_sorted_vectors(pairs: &Vec<String>) -> (Vec<i64>, Vec<i64>) {
-    let (mut f, mut s): (Vec<i64>, Vec<i64>) = pairs
+use crate::common::{parse_lines, split_tuple};
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+struct Pair(i64, i64);
+
+impl FromStr for Pair {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let [first, second] = split_tuple(line, &[' '], "number")?;
+
+        Ok(Pair(first, second))
+    }
+}
+
+fn parse_into_sorted_vectors(pairs: &[String]) -> Result<(Vec<i64>, Vec<i64>)> {
+    let (mut f, mut s): (Vec<i64>, Vec<i64>) = parse_lines::<Pair>(pairs)?
         .into_iter()
-        .filter_map(|line| {
-            let mut parts = line.splitn(2, ' ');
-            let first = parts.next()?.trim().parse::<i64>().unwrap();
-            let second = parts.next()?.trim().parse::<i64>().unwrap();
-            Some((first, second))
-        })
+        .map(|pair| (pair.0, pair.1))
         .unzip();
 
     f.sort();
     s.sort();
 
-    (f, s)
+    Ok((f, s))
 }
 
 fn sum_of_differences(f: &[i64], s: &[i64]) -> i64 {
@@ -29,27 +40,33 @@ fn count_score(f: &[i64], s: &[i64]) -> i64 {
     matches.map(|(n, c)| n * (c as i64)).sum()
 }
 
-fn parse_input() -> (Vec<i64>, Vec<i64>) {
-    parse_into_sorted_vectors(&import("/workspaces/Advent-2024/src/day1/input.txt"))
-}
+pub struct Day1;
 
-pub fn part1() {
-    let s = parse_input();
+impl Solution for Day1 {
+    const DAY: u32 = 1;
 
-    println!("Day 1 part 1 : {}", sum_of_differences(&s.0, &s.1));
-}
+    type Input = (Vec<i64>, Vec<i64>);
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-pub fn part2() {
-    let s = parse_input();
+    fn parse(input: &[String]) -> Result<Self::Input> {
+        parse_into_sorted_vectors(input)
+    }
 
-    println!("Day 1 part 2 : {}", count_score(&s.0, &s.1));
+    fn part1(s: &Self::Input) -> Self::Answer1 {
+        sum_of_differences(&s.0, &s.1)
+    }
+
+    fn part2(s: &Self::Input) -> Self::Answer2 {
+        count_score(&s.0, &s.1)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use itertools::Itertools;
-    use std::{fmt::Debug, str::FromStr};
+    use std::fmt::Debug;
 
     static LISTS: [&str; 2] = ["3 4 2 1 3 3", "4 3 5 3 9 3"];
 
@@ -79,4 +96,17 @@ mod tests {
 
         assert_eq!(score, 31);
     }
+
+    #[test]
+    fn invalid() {
+        let e = parse_into_sorted_vectors(&["3   4".to_string(), "4   x3".to_string()])
+            .err()
+            .unwrap();
+
+        assert_eq!((e.line, e.column), (Some(2), Some(5)));
+
+        let e = parse_into_sorted_vectors(&["3".to_string()]).err().unwrap();
+
+        assert_eq!((e.line, e.column), (Some(1), Some(2)));
+    }
 }
@@ -1,9 +1,11 @@
 use std::str::FromStr;
 
-use crate::common::import;
+use crate::common::{parse_lines, split_integers};
+use crate::error::{Error, Result};
+use crate::solution::Solution;
 
 #[derive(Debug)]
-struct Report {
+pub struct Report {
     levels: Vec<i64>,
 }
 impl Report {
@@ -12,18 +14,12 @@ impl Report {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseReportError;
-
 impl FromStr for Report {
-    type Err = ParseReportError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         Ok(Report {
-            levels: s
-                .split_whitespace()
-                .map(|s| s.parse::<i64>().unwrap())
-                .collect(),
+            levels: split_integers(s, &[' ', '\t'], "level")?,
         })
     }
 }
@@ -57,24 +53,26 @@ impl Safe for Report {
     }
 }
 
-pub fn part1() {
-    let safe_reports = import("/workspaces/Advent-2024/src/day2/input.txt")
-        .iter()
-        .map(|line| Report::from_str(line).unwrap())
-        .filter(|r| r.is_safe())
-        .count();
+pub struct Day2;
 
-    println!("Day 2 part 1 : {}", safe_reports);
-}
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Report>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &[String]) -> Result<Self::Input> {
+        parse_lines(input)
+    }
 
-pub fn part2() {
-    let safe_reports = import("/workspaces/Advent-2024/src/day2/input.txt")
-        .iter()
-        .map(|line| Report::from_str(line).unwrap())
-        .filter(|r| r.is_safe_when_dampened())
-        .count();
+    fn part1(reports: &Self::Input) -> Self::Answer1 {
+        reports.iter().filter(|r| r.is_safe()).count()
+    }
 
-    println!("Day 2 part 2 : {}", safe_reports);
+    fn part2(reports: &Self::Input) -> Self::Answer2 {
+        reports.iter().filter(|r| r.is_safe_when_dampened()).count()
+    }
 }
 
 #[cfg(test)]
@@ -82,8 +80,9 @@ mod tests {
     use std::str::FromStr;
 
     use crate::day2::Safe;
+    use crate::solution::Solution;
 
-    use super::Report;
+    use super::{Day2, Report};
 
     const INPUT: [&str; 6] = [
         "7 6 4 2 1",
@@ -115,4 +114,22 @@ mod tests {
             .count();
         assert_eq!(safe, 4)
     }
+
+    #[test]
+    fn solution() {
+        let input = INPUT.map(|s| s.to_string());
+        let reports = Day2::parse(&input).unwrap();
+
+        assert_eq!(Day2::part1(&reports), 2);
+        assert_eq!(Day2::part2(&reports), 4);
+    }
+
+    #[test]
+    fn invalid() {
+        let e = Day2::parse(&["7 6 4".to_string(), "1 2 +x 8".to_string()])
+            .err()
+            .unwrap();
+
+        assert_eq!(e.to_string(), "2:5: '+x' is not a level");
+    }
 }
@@ -1,84 +1,119 @@
-use std::fs;
-
 use regex::Regex;
 
-struct Mul(i64, i64);
-
-fn parse_muls(input: &str) -> Vec<Mul> {
-    let re = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
+use crate::error::{Error, Result};
+use crate::solution::Solution;
 
-    re.captures_iter(input)
-        .map(|f| {
-            Mul(
-                f.get(1).unwrap().as_str().parse::<i64>().unwrap(),
-                f.get(2).unwrap().as_str().parse::<i64>().unwrap(),
-            )
-        })
-        .collect()
+/// An instruction found in the corrupted memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(i64, i64),
+    Do,
+    Dont,
 }
 
-fn parse_muls_repaired(input: &str) -> Vec<Mul> {
+/// Every instruction in the corrupted memory, in order. Everything else is noise.
+fn parse_instructions(input: &[String]) -> Result<Vec<Instruction>> {
     let re = Regex::new(r"mul\((\d+),(\d+)\)|don't|do").unwrap();
 
-    let mut skip = false;
+    let mut instructions = Vec::new();
+    for (i, line) in input.iter().enumerate() {
+        for captures in re.captures_iter(line) {
+            let operand = |group| {
+                let digits = captures.get(group).unwrap().as_str();
+                digits.parse::<i64>().map_err(|_| {
+                    Error::token(line, digits, format!("'{}' is out of range", digits))
+                        .at_line(i + 1)
+                })
+            };
+
+            instructions.push(match &captures[0] {
+                "do" => Instruction::Do,
+                "don't" => Instruction::Dont,
+                _ => Instruction::Mul(operand(1)?, operand(2)?),
+            });
+        }
+    }
+
+    Ok(instructions)
+}
 
-    re.captures_iter(input)
-        .filter_map(|captures| {
-            let m = captures.get(0).unwrap().as_str();
+/// Sum of all multiplications.
+fn sum_muls(instructions: &[Instruction]) -> i64 {
+    instructions
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::Mul(a, b) => a * b,
+            _ => 0,
+        })
+        .sum()
+}
 
-            let mut result = None;
+/// Sum of the multiplications that are not disabled by a preceding `don't`.
+fn sum_muls_repaired(instructions: &[Instruction]) -> i64 {
+    let mut skip = false;
 
-            if m == "do" {
+    instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            Instruction::Do => {
                 skip = false;
-            } else if m == "don't" {
+                None
+            }
+            Instruction::Dont => {
                 skip = true;
-            } else if !skip {
-                result = Some(Mul(
-                    captures.get(1).unwrap().as_str().parse::<i64>().unwrap(),
-                    captures.get(2).unwrap().as_str().parse::<i64>().unwrap(),
-                ))
+                None
             }
-
-            result
+            Instruction::Mul(a, b) => (!skip).then_some(a * b),
         })
-        .collect()
+        .sum()
 }
 
-pub fn part1() {
-    let input = fs::read_to_string("/workspaces/Advent-2024/src/day3/input.txt").unwrap();
+pub struct Day3;
 
-    let sum = parse_muls(&input)
-        .iter()
-        .fold(0i64, |acc, e| acc + e.0 * e.1);
+impl Solution for Day3 {
+    const DAY: u32 = 3;
 
-    println!("Day 3 part 1 : {}", sum);
-}
+    /// The corrupted memory is a single program, `don't` on one line still holds on the next.
+    type Input = Vec<Instruction>;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-pub fn part2() {
-    let input = fs::read_to_string("/workspaces/Advent-2024/src/day3/input.txt").unwrap();
+    fn parse(input: &[String]) -> Result<Self::Input> {
+        parse_instructions(input)
+    }
 
-    let sum = parse_muls_repaired(&input)
-        .iter()
-        .fold(0i64, |acc, e| acc + e.0 * e.1);
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        sum_muls(input)
+    }
 
-    println!("Day 3 part 2 : {}", sum);
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        sum_muls_repaired(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_muls, parse_muls_repaired};
+    use super::*;
+
+    fn parse(input: &str) -> Vec<Instruction> {
+        Day3::parse(&[input.to_string()]).unwrap()
+    }
 
     #[test]
     fn part1() {
         let input: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
 
-        let muls = parse_muls(input);
-
-        assert_eq!(muls.len(), 4);
+        let instructions = parse(input);
 
-        let sum = muls.iter().fold(0i64, |acc, e| acc + e.0 * e.1);
+        assert_eq!(
+            instructions
+                .iter()
+                .filter(|i| matches!(i, Instruction::Mul(..)))
+                .count(),
+            4
+        );
 
-        assert_eq!(sum, 161);
+        assert_eq!(Day3::part1(&instructions), 161);
     }
 
     #[test]
@@ -86,10 +121,21 @@ mod tests {
         let input: &str =
             "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
-        let muls = parse_muls_repaired(input);
+        assert_eq!(Day3::part2(&parse(input)), 48);
+
+        let lines = ["mul(2,4)don't()".to_string(), "mul(5,5)".to_string()];
+        assert_eq!(Day3::part2(&Day3::parse(&lines).unwrap()), 8);
+    }
+
+    #[test]
+    fn invalid() {
+        let input = [
+            "mul(1,2)".to_string(),
+            "xmul(99999999999999999999,2)".to_string(),
+        ];
 
-        let sum = muls.iter().fold(0i64, |acc, e| acc + e.0 * e.1);
+        let e = Day3::parse(&input).err().unwrap();
 
-        assert_eq!(sum, 48);
+        assert_eq!(e.to_string(), "2:6: '99999999999999999999' is out of range");
     }
 }
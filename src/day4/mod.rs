@@ -1,94 +1,84 @@
-use std::collections::HashMap;
-
 use crate::common::*;
+use crate::error::Result;
+use crate::solution::Solution;
 
-pub fn part1() {
-    let map = parse_map(&import("/workspaces/Advent-2024/src/day4/input.txt"));
+pub struct Day4;
 
-    println!("Day 4 part 1 : {}", count_xmas_lines(&map));
-}
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &[String]) -> Result<Self::Input> {
+        parse_map(input)
+    }
 
-pub fn part2() {
-    let map = parse_map(&import("/workspaces/Advent-2024/src/day4/input.txt"));
+    fn part1(map: &Self::Input) -> Self::Answer1 {
+        count_xmas_lines(map)
+    }
 
-    println!("Day 4 part 2 : {}", count_x_mas_lines(&map));
+    fn part2(map: &Self::Input) -> Self::Answer2 {
+        count_x_mas_lines(map)
+    }
 }
 
-fn parse_map(input: &Vec<String>) -> HashMap<Point, char> {
-    input
-        .iter()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().map(move |(x, c)| {
-                (
-                    Point {
-                        x: x as i64,
-                        y: y as i64,
-                    },
-                    c,
-                )
-            })
-        })
+fn parse_map(input: &[String]) -> Result<Grid<char>> {
+    Grid::try_from(input)
+}
+
+/// Every horizontal, vertical and diagonal line of the grid, read forwards. Words spelled
+/// backwards are found by searching these for the reversed word.
+fn lines(points: &Grid<char>) -> Vec<String> {
+    let rows = points.rows().map(|row| row.iter().collect());
+    let columns = points.columns().map(|column| column.collect());
+    let diagonals = points.diagonals().map(|line| line.collect());
+    let anti_diagonals = points.anti_diagonals().map(|line| line.collect());
+
+    rows.chain(columns)
+        .chain(diagonals)
+        .chain(anti_diagonals)
         .collect()
 }
 
-fn count_xmas_lines(points: &HashMap<Point, char>) -> usize {
-    points
-        .keys()
-        .map(|location| {
-            [
-                xmas_line(points, location, &[(0, 0), (1, 0), (2, 0), (3, 0)]), // EAST
-                xmas_line(points, location, &[(0, 0), (1, 1), (2, 2), (3, 3)]), // SOUTH-EAST
-                xmas_line(points, location, &[(0, 0), (0, 1), (0, 2), (0, 3)]), // SOUTH
-                xmas_line(points, location, &[(0, 0), (-1, 1), (-2, 2), (-3, 3)]), // SOUTH-WEST
-                xmas_line(points, location, &[(0, 0), (-1, 0), (-2, 0), (-3, 0)]), // WEST
-                xmas_line(points, location, &[(0, 0), (-1, -1), (-2, -2), (-3, -3)]), // NORTH-WEST
-                xmas_line(points, location, &[(0, 0), (0, -1), (0, -2), (0, -3)]), // NORTH
-                xmas_line(points, location, &[(0, 0), (1, -1), (2, -2), (3, -3)]), // NORTH-EAST
-            ]
-            .iter()
-            .filter(|&s| s == "XMAS")
-            .count()
-        })
+fn count_xmas_lines(points: &Grid<char>) -> usize {
+    lines(points)
+        .iter()
+        .map(|line| line.matches("XMAS").count() + line.matches("SAMX").count())
         .sum()
 }
 
-fn xmas_line(points: &HashMap<Point, char>, location: &Point, offsets: &[(i64, i64)]) -> String {
-    offsets
+/// True when both diagonals of a 3 by 3 view spell "MAS" in either direction.
+fn is_x_mas(view: &GridView<char>) -> bool {
+    let word = |points: [(i64, i64); 3]| {
+        points
+            .iter()
+            .map(|&p| view[&Point::from(p)])
+            .collect::<String>()
+    };
+
+    view.width() == 3
+        && view.height() == 3
+        && [
+            word([(0, 0), (1, 1), (2, 2)]),
+            word([(2, 0), (1, 1), (0, 2)]),
+        ]
         .iter()
-        .filter_map(|o| {
-            if let Some(p) = points.get(&location.offset(o)) {
-                Some(p)
-            } else {
-                None
-            }
-        })
-        .collect()
+        .all(|word| word == "MAS" || word == "SAM")
 }
 
-fn count_x_mas_lines(points: &HashMap<Point, char>) -> usize {
+fn count_x_mas_lines(points: &Grid<char>) -> usize {
     points
-        .keys()
-        .filter_map(|location| {
-            points
-                .get(&location)
-                .and_then(|c| if *c == 'A' { Some(location) } else { None })
-        })
-        .map(|location| {
-            [
-                xmas_line(points, location, &[(-1, -1), (0, 0), (1, 1)]),
-                xmas_line(points, location, &[(-1, 1), (0, 0), (1, -1)]),
-            ]
-        })
-        .filter(|lines| {
-            (lines[0] == "MAS" || lines[0] == "SAM") && (lines[1] == "MAS" || lines[1] == "SAM")
-        })
+        .points()
+        .filter_map(|corner| points.view(&Rect::new(corner, corner + Point { x: 2, y: 2 })))
+        .filter(is_x_mas)
         .count()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day4::{count_xmas_lines, import, parse_map};
+    use crate::day4::{count_xmas_lines, parse_map};
 
     use super::count_x_mas_lines;
 
@@ -107,7 +97,7 @@ mod tests {
             "MXMXAXMASX",
         ];
 
-        let map = parse_map(&input.iter().map(|f| f.to_string()).collect());
+        let map = parse_map(&input.iter().map(|f| f.to_string()).collect::<Vec<_>>()).unwrap();
 
         assert_eq!(map.len(), 100);
 
@@ -131,7 +121,7 @@ mod tests {
             "MXMXAXMASX",
         ];
 
-        let map = parse_map(&input.iter().map(|f| f.to_string()).collect());
+        let map = parse_map(&input.iter().map(|f| f.to_string()).collect::<Vec<_>>()).unwrap();
         let n = count_x_mas_lines(&map);
         assert_eq!(n, 9);
     }
@@ -2,42 +2,64 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 
-use crate::common::import;
+use crate::common::{parse_lines, split_integers, split_tuple, Cycle, Graph};
+use crate::error::{Error, Result};
+use crate::solution::Solution;
 
-pub fn part1() {
-    let rules = parse_rules(&import("/workspaces/Advent-2024/src/day5/input_rules.txt"));
-    let updates = parse_updates(&import(
-        "/workspaces/Advent-2024/src/day5/input_updates.txt",
-    ));
+pub struct Day5;
 
-    let valid_updates = valid_updates(&updates, &rules);
+impl Solution for Day5 {
+    const DAY: u32 = 5;
 
-    println!(
-        "Day 5 part 1 : {}",
-        valid_updates
+    /// The rules section and the updates section are separated by an empty line. Every rule is
+    /// an edge from the page that goes before to the page that goes after.
+    type Input = (Graph<i64>, Vec<Update>);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &[String]) -> Result<Self::Input> {
+        let separator = input
             .iter()
-            .fold(0i64, |acc, e| acc + e.middle_page())
-    );
-}
+            .position(|line| line.is_empty())
+            .ok_or_else(|| {
+                Error::parse("expected an empty line between the rules and the updates")
+            })?;
+
+        let rules = parse_rules(&input[..separator])?;
+        let updates =
+            parse_updates(&input[separator + 1..]).map_err(|e| e.after_lines(separator + 1))?;
+
+        for (i, update) in updates.iter().enumerate() {
+            update.sort(&rules).map_err(|cycle| {
+                Error::parse(format!("the rules for this update form a cycle: {}", cycle))
+                    .at_line(separator + i + 2)
+            })?;
+        }
 
-pub fn part2() {
-    let rules = parse_rules(&import("/workspaces/Advent-2024/src/day5/input_rules.txt"));
-    let updates = parse_updates(&import(
-        "/workspaces/Advent-2024/src/day5/input_updates.txt",
-    ));
+        Ok((rules, updates))
+    }
 
-    let valid_updates = valid_updates(&updates, &rules);
+    fn part1((rules, updates): &Self::Input) -> Self::Answer1 {
+        valid_updates(updates, rules)
+            .iter()
+            .fold(0i64, |acc, e| acc + e.middle_page())
+    }
 
-    let invalid_updates = updates
-        .iter()
-        .filter(|u| !valid_updates.contains(u))
-        .collect::<Vec<_>>();
+    fn part2((rules, updates): &Self::Input) -> Self::Answer2 {
+        let valid_updates = valid_updates(updates, rules);
 
-    let corrected_updates = invalid_updates.iter().map(|u| u.sort(&rules));
+        let invalid_updates = updates
+            .iter()
+            .filter(|u| !valid_updates.contains(u))
+            .collect::<Vec<_>>();
 
-    let sum_of_middle_pages = corrected_updates.fold(0i64, |acc, e| acc + e.middle_page());
+        let corrected_updates = invalid_updates.iter().map(|u| {
+            u.sort(rules)
+                .expect("parse rejects updates with cyclic rules")
+        });
 
-    println!("Day 5 part 2 : {}", sum_of_middle_pages);
+        corrected_updates.fold(0i64, |acc, e| acc + e.middle_page())
+    }
 }
 
 #[derive(Debug)]
@@ -47,7 +69,7 @@ struct Rule {
 }
 
 #[derive(Debug, PartialEq)]
-struct Update {
+pub struct Update {
     pages: Vec<i64>,
 }
 
@@ -56,116 +78,62 @@ impl Update {
         self.pages[self.pages.len() / 2]
     }
 
-    fn sort(&self, rules: &[Rule]) -> Update {
-        Update {
-            pages: self
-                .pages
-                .iter()
-                .sorted_by(|&a, &b| {
-                    if let Some(rule) = rules.iter().find(|rule| {
-                        (rule.before == *a && rule.after == *b)
-                            || (rule.before == *b && rule.after == *a)
-                    }) {
-                        if rule.before == *a && rule.after == *b {
-                            std::cmp::Ordering::Less
-                        } else {
-                            std::cmp::Ordering::Greater
-                        }
-                    } else {
-                        std::cmp::Ordering::Equal
-                    }
-                })
-                .map(|e| *e)
-                .collect::<Vec<_>>(),
-        }
+    /// The pages reordered so that every rule between them holds, or the rules between them that
+    /// form a cycle.
+    fn sort(&self, rules: &Graph<i64>) -> std::result::Result<Update, Cycle<i64>> {
+        Ok(Update {
+            pages: rules.topological_sort_of(&self.pages)?,
+        })
     }
 }
 
-#[derive(Debug)]
-struct OrderParseError;
-
 impl FromStr for Rule {
-    type Err = OrderParseError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let fields = s.split_once('|').unwrap();
-        Ok(Rule {
-            before: fields.0.parse().unwrap(),
-            after: fields.1.parse().unwrap(),
-        })
+    fn from_str(s: &str) -> Result<Self> {
+        let [before, after] = split_tuple(s, &['|'], "page")?;
+
+        Ok(Rule { before, after })
     }
 }
 
-#[derive(Debug)]
-struct UpdateParseError;
-
 impl FromStr for Update {
-    type Err = UpdateParseError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         Ok(Update {
-            pages: s
-                .split(',')
-                .map(|token| token.parse::<i64>().unwrap())
-                .collect::<Vec<_>>(),
+            pages: split_integers(s, &[','], "page")?,
         })
     }
 }
 
-impl Rule {
-    fn applies_to(&self, update: &Update) -> bool {
-        update.pages.contains(&self.before) && update.pages.contains(&self.after)
-    }
-
-    fn is_valid(&self, update: &Update) -> bool {
-        let before_index = update
-            .pages
-            .iter()
-            .position(|page| *page == self.before)
-            .unwrap();
-
-        let after_index = update
-            .pages
-            .iter()
-            .position(|page| *page == self.after)
-            .unwrap();
-
-        before_index < after_index
-    }
-}
-
-fn parse_rules(input: &Vec<String>) -> Vec<Rule> {
-    input
-        .iter()
-        .map(|s| s.parse::<Rule>().unwrap())
-        .collect::<Vec<_>>()
+fn parse_rules(input: &[String]) -> Result<Graph<i64>> {
+    Ok(parse_lines::<Rule>(input)?
+        .into_iter()
+        .map(|rule| (rule.before, rule.after))
+        .collect())
 }
 
-fn parse_updates(input: &Vec<String>) -> Vec<Update> {
-    input
-        .iter()
-        .map(|s| s.parse::<Update>().unwrap())
-        .collect::<Vec<_>>()
+fn parse_updates(input: &[String]) -> Result<Vec<Update>> {
+    parse_lines(input)
 }
 
-/// For each update in updates:
-/// - Find all rules that apply to this update
-/// - And then check if all those rules apply.
-///
-///
-fn valid_updates<'a>(updates: &'a Vec<Update>, rules: &'a Vec<Rule>) -> Vec<&'a Update> {
+/// The updates that are already in the right order.
+fn valid_updates<'a>(updates: &'a [Update], rules: &Graph<i64>) -> Vec<&'a Update> {
     updates
         .iter()
         .filter(|update| is_valid_update(update, rules))
         .collect::<Vec<_>>()
 }
 
-/// Returns if the update is valid with given rules
-fn is_valid_update(update: &Update, rules: &Vec<Rule>) -> bool {
-    rules
+/// Returns if the update is valid with given rules, i.e. no page comes after a page that it
+/// must go before.
+fn is_valid_update(update: &Update, rules: &Graph<i64>) -> bool {
+    update
+        .pages
         .iter()
-        .filter(|rule| rule.applies_to(&update))
-        .all(|rule| rule.is_valid(update))
+        .tuple_combinations()
+        .all(|(a, b)| !rules.has_edge(b, a))
 }
 
 #[cfg(test)]
@@ -181,11 +149,17 @@ mod tests {
             "47|29", "75|13", "53|13",
         ];
 
-        let rules = parse_rules(&rules_input.iter().map(|s| s.to_string()).collect());
+        let rules = parse_rules(
+            &rules_input
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
 
-        assert!(rules.len() == 21);
+        assert!(rules.edge_count() == 21);
 
-        let updates_input = vec![
+        let updates_input = [
             "75,47,61,53,29",
             "97,61,53,29,13",
             "75,29,13",
@@ -194,7 +168,13 @@ mod tests {
             "97,13,75,29,47",
         ];
 
-        let updates = parse_updates(&updates_input.iter().map(|s| s.to_string()).collect());
+        let updates = parse_updates(
+            &updates_input
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
 
         assert!(updates.len() == 6);
 
@@ -224,11 +204,17 @@ mod tests {
             "47|29", "75|13", "53|13",
         ];
 
-        let rules = parse_rules(&rules_input.iter().map(|s| s.to_string()).collect());
+        let rules = parse_rules(
+            &rules_input
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
 
-        assert!(rules.len() == 21);
+        assert!(rules.edge_count() == 21);
 
-        let updates_input = vec![
+        let updates_input = [
             "75,47,61,53,29",
             "97,61,53,29,13",
             "75,29,13",
@@ -237,7 +223,13 @@ mod tests {
             "97,13,75,29,47",
         ];
 
-        let updates = parse_updates(&updates_input.iter().map(|s| s.to_string()).collect());
+        let updates = parse_updates(
+            &updates_input
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
 
         assert!(updates.len() == 6);
 
@@ -248,11 +240,99 @@ mod tests {
             .filter(|u| !valid_updates.contains(u))
             .collect::<Vec<_>>();
 
-        let corrected_updates = invalid_updates.iter().map(|u| u.sort(&rules));
+        let corrected_updates = invalid_updates.iter().map(|u| u.sort(&rules).unwrap());
 
         assert_eq!(corrected_updates.len(), 3);
         let sum_of_middle_pages = corrected_updates.fold(0i64, |acc, e| acc + e.middle_page());
 
         assert_eq!(sum_of_middle_pages, 123);
     }
+
+    #[test]
+    fn solution() {
+        let input = [
+            "47|53",
+            "97|13",
+            "97|61",
+            "97|47",
+            "75|29",
+            "61|13",
+            "75|53",
+            "29|13",
+            "97|29",
+            "53|29",
+            "61|53",
+            "97|53",
+            "61|29",
+            "47|13",
+            "75|47",
+            "97|75",
+            "47|61",
+            "75|61",
+            "47|29",
+            "75|13",
+            "53|13",
+            "",
+            "75,47,61,53,29",
+            "97,61,53,29,13",
+            "75,29,13",
+            "75,97,47,61,53",
+            "61,13,29",
+            "97,13,75,29,47",
+        ]
+        .map(|s| s.to_string());
+
+        let parsed = Day5::parse(&input).unwrap();
+
+        assert_eq!(Day5::part1(&parsed), 143);
+        assert_eq!(Day5::part2(&parsed), 123);
+    }
+
+    #[test]
+    fn invalid() {
+        let input = ["47|53", "97|13", "", "75,47,61", "97,6x1,53"].map(|s| s.to_string());
+
+        let e = Day5::parse(&input).err().unwrap();
+
+        assert_eq!(e.to_string(), "5:4: '6x1' is not a page");
+
+        let e = Day5::parse(&input[..2]).err().unwrap();
+
+        assert_eq!(
+            e.to_string(),
+            "expected an empty line between the rules and the updates"
+        );
+    }
+
+    #[test]
+    fn empty_pages() {
+        let input = ["47|53", "", "75,,61"].map(|s| s.to_string());
+
+        let e = Day5::parse(&input).err().unwrap();
+
+        assert_eq!(e.to_string(), "3:4: '' is not a page");
+
+        let input = ["47|53", "||1|2", "", "75,61"].map(|s| s.to_string());
+
+        let e = Day5::parse(&input).err().unwrap();
+
+        assert_eq!(e.to_string(), "2:1: '' is not a page");
+    }
+
+    #[test]
+    fn cyclic_rules() {
+        let input = ["1|2", "2|3", "3|1", "", "1,2", "3,1,2"].map(|s| s.to_string());
+
+        let e = Day5::parse(&input).err().unwrap();
+
+        assert_eq!(
+            e.to_string(),
+            "6: the rules for this update form a cycle: 1 -> 2 -> 3 -> 1"
+        );
+
+        let update = "2,1,2".parse::<Update>().unwrap();
+        let rules = Graph::from_iter([(1, 2), (2, 1)]);
+
+        assert_eq!(update.sort(&rules).unwrap_err().edges, [(1, 2), (2, 1)]);
+    }
 }
@@ -1,186 +1,136 @@
-use crate::common::{import, Direction, Point, Rotation};
-use core::panic;
-use std::collections::HashMap;
+use crate::common::{
+    find_cycle_brent, Cardinal, Direction, Grid, Outcome, Point, Rect, Rotation, Visited,
+};
+use crate::error::{Error, Result};
+use crate::solution::{Solution, Unsolved};
 
-pub fn part1() {
-    let mut map = Map::from(&import("/workspaces/Advent-2024/src/day6/input.txt"));
-    while !map.r#move() {
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    const SOLVED: [bool; 2] = [true, false];
+
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = Unsolved;
+
+    fn parse(input: &[String]) -> Result<Self::Input> {
+        Map::try_from(input)
     }
-    //map.print();
 
-    println!("Day 6 part 1 : {}", map.visited().len());
-}
+    fn part1(map: &Self::Input) -> Self::Answer1 {
+        map.route.len()
+    }
 
-pub fn part2() {
-    println!("Day 6 part 2 :");
+    fn part2(_map: &Self::Input) -> Self::Answer2 {
+        Unsolved
+    }
 }
 
 #[derive(Clone, PartialEq)]
 enum Status {
     Normal,
     Obstruction,
-    Visited { direction: Direction },
+    Visited { direction: Cardinal },
 }
 
-impl From<char> for Status {
-    fn from(value: char) -> Self {
-        match value {
-            '^' => Status::Visited {
-                direction: Direction::North,
-            },
-            '>' => Status::Visited {
-                direction: Direction::East,
-            },
-            'v' => Status::Visited {
-                direction: Direction::South,
-            },
-            '<' => Status::Visited {
-                direction: Direction::West,
-            },
+impl TryFrom<char> for Status {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self> {
+        Ok(match value {
             '.' => Status::Normal,
             '#' => Status::Obstruction,
-            _ => panic!(),
-        }
+            '^' | '>' | 'v' | '<' => Status::Visited {
+                direction: Cardinal::try_from(value)?,
+            },
+            _ => return Err(Error::parse(format!("unexpected '{}' in the map", value))),
+        })
     }
 }
 
 impl From<Status> for char {
     fn from(value: Status) -> Self {
         match value {
-            Status::Visited {
-                direction: Direction::North,
-            } => '^',
-            Status::Visited {
-                direction: Direction::East,
-            } => '>',
-            Status::Visited {
-                direction: Direction::South,
-            } => 'v',
-            Status::Visited {
-                direction: Direction::West,
-            } => '<',
+            Status::Visited { direction } => Direction::from(direction).arrow(),
             Status::Normal => '.',
             Status::Obstruction => '#',
-            _ => panic!(),
         }
     }
 }
 
-struct Map {
-    locations: HashMap<Point, Status>,
-    current: (Point, Direction),
+#[derive(Clone)]
+pub struct Map {
+    locations: Grid<Status>,
+    bounds: Rect,
+    current: (Point, Cardinal),
+    /// The positions the guard visits before leaving the map.
+    route: Visited,
 }
 
 impl Map {
-    fn x_range(&self) -> std::ops::Range<i64> {
-        0..self
-            .locations
-            .keys()
-            .max_by_key(|k| k.x)
-            .map(|k| k.x + 1)
-            .unwrap()
-    }
-
-    fn y_range(&self) -> std::ops::Range<i64> {
-        0..self
-            .locations
-            .keys()
-            .max_by_key(|k| k.y)
-            .map(|k| k.y + 1)
-            .unwrap()
-    }
-
+    #[cfg(test)]
     fn print(&self) {
-        for y in self.y_range() {
-            for x in self.x_range() {
-                if let Some(status) = self.locations.get(&Point { x, y }) {
-                    print!("{}", char::from(status.clone()));
-                } else {
-                    panic!()
-                }
-            }
-            println!();
-        }
+        let mut locations = self.locations.map(|status| char::from(status.clone()));
+        self.route.iter().for_each(|p| locations[&p] = 'X');
+        print!("{}", locations);
     }
 
-    fn next_position(&self) -> Option<(&Point, &Status)> {
-        self.locations
-            .get_key_value(&self.current.0.clone().r#move(&self.current.1))
-    }
-
-    fn r#move(&mut self) -> bool {
-        if let Some((p, s)) = self.next_position() {
-            match s {
-                Status::Obstruction => {
-                    self.current.1 = self.current.1.rotate(&Rotation::CW).rotate(&Rotation::CW);
-                }
-                _ => {
-                    self.current.0 = p.clone();
-                }
-            }
-
-            if let Some(s) = self.locations.get_mut(&self.current.0) {
-                *s = Status::Visited {
-                    direction: self.current.1.clone(),
-                }
-            }
-
-            false
-        } else {
-            true
+    /// Where the guard at `position` goes next, turning right when an obstruction is in the way.
+    /// `None` once the guard leaves the map.
+    fn step(&self, (position, facing): &(Point, Cardinal)) -> Option<(Point, Cardinal)> {
+        let next = *position + facing.offset();
+        if !self.bounds.contains(&next) {
+            return None;
+        }
+        match self.locations[&next] {
+            Status::Obstruction => Some((*position, facing.rotate(&Rotation::CW))),
+            _ => Some((next, *facing)),
         }
     }
 
-    fn visited(&self) -> Vec<Point> {
-        self.locations
-            .iter()
-            .filter_map(|(p, s)| match s {
-                Status::Visited { .. } => Some(p.clone()),
-                _ => None,
-            })
-            .collect()
+    /// The positions the guard visits before leaving the map, `None` when the guard never leaves.
+    fn walk(&self) -> Option<Visited> {
+        let mut visited = Visited::new(self.locations.width(), self.locations.height());
+        let outcome = find_cycle_brent(self.current, |state| {
+            visited.insert(&state.0);
+            self.step(state)
+        });
+
+        matches!(outcome, Outcome::Terminated { .. }).then_some(visited)
     }
 }
 
-impl From<&Vec<String>> for Map {
-    fn from(value: &Vec<String>) -> Self {
-        let locations = value
-            .iter()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars().enumerate().map(move |(x, c)| {
-                    (
-                        Point {
-                            x: x as i64,
-                            y: y as i64,
-                        },
-                        Status::from(c),
-                    )
-                })
-            })
-            .collect::<Vec<_>>();
-
-        locations
-            .iter()
-            .find(|(_, s)| {
-                s == &Status::Visited {
-                    direction: Direction::North,
-                }
-            })
-            .map(|f| Map {
-                current: (f.0.clone(), Direction::North),
-                locations: locations.clone().into_iter().collect(),
-            })
-            .expect("")
+impl TryFrom<&[String]> for Map {
+    type Error = Error;
+
+    fn try_from(value: &[String]) -> Result<Self> {
+        let (locations, markers) = Grid::parse_with(value, &['^'], Status::try_from)?;
+
+        let (start, bounds) = markers[&'^']
+            .first()
+            .zip(locations.bounds())
+            .ok_or_else(|| Error::parse("the map has no guard facing north ('^')"))?;
+
+        let mut map = Map {
+            current: (*start, Cardinal::North),
+            bounds,
+            route: Visited::new(locations.width(), locations.height()),
+            locations,
+        };
+        map.route = map
+            .walk()
+            .ok_or_else(|| Error::parse("the guard walks in a loop"))?;
+
+        Ok(map)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        common::Point,
-        day6::{Map, Status},
-    };
+    use crate::common::{find_cycle_brent, Outcome, Point};
+    use crate::day6::{Day6, Map};
+    use crate::solution::Solution;
 
     #[test]
     fn part1() {
@@ -197,25 +147,73 @@ mod tests {
             "......#...".to_string(),
         ];
 
-        let mut map = Map::from(&input);
+        let map = Map::try_from(input.as_slice()).unwrap();
 
         map.print();
         println!();
 
         assert_eq!(map.current.0, Point { x: 4, y: 6 });
 
-        while !map.r#move() {
-            map.print();
-            println!();
-        }
+        let Outcome::Terminated { last, .. } =
+            find_cycle_brent(map.current, |state| map.step(state))
+        else {
+            panic!("the guard walks in a loop");
+        };
 
-        assert_eq!(map.current.0, Point { x: 7, y: 9 });
+        assert_eq!(last.0, Point { x: 7, y: 9 });
 
-        assert_eq!(map.visited().len(), 41);
+        assert_eq!(map.route.len(), 41);
     }
 
     #[test]
+    #[ignore = "part 2 is not solved yet"]
     fn part2() {
-        assert!(false)
+        let input = vec![
+            "....#.....".to_string(),
+            ".........#".to_string(),
+            "..........".to_string(),
+            "..#.......".to_string(),
+            ".......#..".to_string(),
+            "..........".to_string(),
+            ".#..^.....".to_string(),
+            "........#.".to_string(),
+            "#.........".to_string(),
+            "......#...".to_string(),
+        ];
+
+        let map = Map::try_from(input.as_slice()).unwrap();
+
+        assert_eq!(Day6::part2(&map).to_string(), "6");
+    }
+
+    #[test]
+    fn invalid() {
+        let e = Map::try_from(["..#.".to_string(), ".^x.".to_string()].as_slice())
+            .err()
+            .unwrap();
+
+        assert_eq!(e.to_string(), "2:3: unexpected 'x' in the map");
+
+        let e = Map::try_from([".^.".to_string(), "N..".to_string()].as_slice())
+            .err()
+            .unwrap();
+
+        assert_eq!(e.to_string(), "2:1: unexpected 'N' in the map");
+
+        let e = Map::try_from(["..#.".to_string()].as_slice())
+            .err()
+            .unwrap();
+
+        assert_eq!(e.to_string(), "the map has no guard facing north ('^')");
+
+        let e = Map::try_from(
+            [".#..", "...#", "#^..", "..#."]
+                .map(String::from)
+                .as_slice(),
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(e.to_string(), "the guard walks in a loop");
     }
 }
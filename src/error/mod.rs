@@ -0,0 +1,164 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Error for everything that reads or parses puzzle input. Parsers fill in the column, the
+/// line and file are added by the callers that know them.
+#[derive(Debug)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub file: Option<PathBuf>,
+    /// Line number, starting at 1.
+    pub line: Option<usize>,
+    /// Column in characters, starting at 1.
+    pub column: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    Io(io::Error),
+    Parse(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse(message: impl Into<String>) -> Self {
+        Error {
+            kind: ErrorKind::Parse(message.into()),
+            file: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Error for `token`, a slice of `line`, reporting where it starts. There is no column when
+    /// `token` is not part of `line`.
+    pub fn token(line: &str, token: &str, message: impl Into<String>) -> Self {
+        let error = Error::parse(message);
+        match column(line, token) {
+            Some(column) => error.at_column(column),
+            None => error,
+        }
+    }
+
+    pub fn in_file(mut self, file: &Path) -> Self {
+        self.file.get_or_insert_with(|| file.to_path_buf());
+        self
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column.get_or_insert(column);
+        self
+    }
+
+    /// Moves the line number down by `lines`, for errors from a parser that was given a part of
+    /// the input that does not start at the first line.
+    pub fn after_lines(mut self, lines: usize) -> Self {
+        self.line = self.line.map(|line| line + lines);
+        self
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error {
+            kind: ErrorKind::Io(e),
+            file: None,
+            line: None,
+            column: None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, "{}:", column)?;
+        }
+        if self.file.is_some() || self.line.is_some() || self.column.is_some() {
+            write!(f, " ")?;
+        }
+
+        match &self.kind {
+            ErrorKind::Io(e) => write!(f, "cannot read input: {}", e),
+            ErrorKind::Parse(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(e) => Some(e),
+            ErrorKind::Parse(_) => None,
+        }
+    }
+}
+
+/// Column, starting at 1, at which `token` starts in `line`. `None` when `token` is not a slice
+/// of `line`.
+pub fn column(line: &str, token: &str) -> Option<usize> {
+    let (within, slice) = (
+        line.as_bytes().as_ptr_range(),
+        token.as_bytes().as_ptr_range(),
+    );
+    if slice.start < within.start || slice.end > within.end {
+        return None;
+    }
+
+    let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+    line.get(..offset).map(|before| before.chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let e = Error::parse("expected a number")
+            .at_column(4)
+            .at_line(2)
+            .in_file(Path::new("input.txt"));
+
+        assert_eq!(e.to_string(), "input.txt:2:4: expected a number");
+        assert_eq!(Error::parse("oops").to_string(), "oops");
+    }
+
+    #[test]
+    fn location_is_kept() {
+        let e = Error::parse("oops").at_line(2).at_line(5).after_lines(10);
+
+        assert_eq!(e.line, Some(12));
+    }
+
+    #[test]
+    fn token_column() {
+        let line = "12 ab 34";
+        let token = line.split(' ').nth(1).unwrap();
+
+        assert_eq!(column(line, token), Some(4));
+        assert_eq!(Error::token(line, token, "oops").column, Some(4));
+        assert_eq!(column(line, &line[8..]), Some(9));
+
+        let wide = "é x";
+        assert_eq!(column(wide, &wide[3..]), Some(3));
+
+        let other = String::from("ab");
+        assert_eq!(column(line, &other), None);
+        assert_eq!(column(&line[3..], &line[..2]), None);
+        assert_eq!(Error::token(line, &other, "oops").column, None);
+    }
+}
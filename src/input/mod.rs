@@ -0,0 +1,59 @@
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::common::{import, import_from};
+use crate::error::Result;
+
+/// Environment variable with the directory holding the `day<N>/input.txt` files.
+pub const INPUTS_VAR: &str = "ADVENT_INPUTS";
+
+/// The inputs live next to the day modules unless [`INPUTS_VAR`] says otherwise.
+pub fn root() -> PathBuf {
+    env::var_os(INPUTS_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("src"))
+}
+
+/// The input file for `day`, `input` takes precedence over the default location.
+pub fn path(day: u32, input: Option<&Path>) -> PathBuf {
+    input
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| root().join(format!("day{}", day)).join("input.txt"))
+}
+
+/// Input path that stands for standard input.
+pub const STDIN: &str = "-";
+
+/// Reads the lines of the input file at `path`, or of stdin when it is [`STDIN`].
+pub fn load(path: &Path) -> Result<Vec<String>> {
+    if path == Path::new(STDIN) {
+        import_from(io::stdin().lock()).map_err(|e| e.in_file(path))
+    } else {
+        import(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_path() {
+        assert!(path(4, None).ends_with("day4/input.txt"));
+        assert_eq!(
+            path(4, Some(Path::new("other.txt"))),
+            PathBuf::from("other.txt")
+        );
+    }
+
+    #[test]
+    fn missing() {
+        let e = load(Path::new("does/not/exist.txt")).unwrap_err();
+
+        assert_eq!(e.file, Some(PathBuf::from("does/not/exist.txt")));
+        assert!(e
+            .to_string()
+            .starts_with("does/not/exist.txt: cannot read input"));
+    }
+}
@@ -0,0 +1,27 @@
+//! Advent of Code 2024 puzzles.
+//!
+//! Every day implements [`solution::Solution`] and is listed in [`SOLUTIONS`]. Shared puzzle
+//! helpers such as [`common::Point`] and [`common::Direction`] live in [`common`].
+
+use solution::Puzzle;
+
+pub mod common;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod error;
+pub mod input;
+pub mod solution;
+
+/// Every implemented day, in order.
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+];
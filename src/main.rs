@@ -1,23 +1,22 @@
-mod common;
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
+use std::process::ExitCode;
 
-fn main() {
-    day1::part1();
-    day1::part2();
-    day2::part1();
-    day2::part2();
-    day3::part1();
-    day3::part2();
-    day4::part1();
-    day4::part2();
-    day5::part1();
-    day5::part2();
-    day6::part1();
-    day6::part2();
-}
+use advent::SOLUTIONS;
+
+mod answers;
+mod cli;
+mod runner;
+mod scaffold;
 
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    match cli::parse(&args) {
+        Ok(options) => runner::run(SOLUTIONS, &options),
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", cli::USAGE);
+            eprintln!("{}", cli::help());
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use report::{Record, Status, CSV_HEADER};
+use timing::Timing;
+
+use crate::answers::{self, Answers};
+use crate::cli::{Command, Format, Options, Selection};
+use crate::scaffold;
+use advent::input;
+use advent::solution::{Parsed, Part, Puzzle};
+
+mod report;
+mod timing;
+
+/// Picks the puzzles and parts to run, `None` if the selected day is not registered.
+fn select<'a>(
+    puzzles: &[&'a dyn Puzzle],
+    selection: &Selection,
+) -> Option<Vec<(&'a dyn Puzzle, Vec<Part>)>> {
+    match selection {
+        Selection::All => Some(
+            puzzles
+                .iter()
+                .map(|&puzzle| (puzzle, Part::BOTH.to_vec()))
+                .collect(),
+        ),
+        Selection::Latest => Some(
+            puzzles
+                .iter()
+                .max_by_key(|puzzle| puzzle.day())
+                .map(|&puzzle| (puzzle, Part::BOTH.to_vec()))
+                .into_iter()
+                .collect(),
+        ),
+        Selection::Day { day, part } => puzzles
+            .iter()
+            .find(|puzzle| puzzle.day() == *day)
+            .map(|&puzzle| vec![(puzzle, part.map_or(Part::BOTH.to_vec(), |part| vec![part]))]),
+    }
+}
+
+/// Runs the selected days and parts. A part that panics is reported and makes the exit code non-zero,
+/// the remaining parts still run.
+pub fn run(puzzles: &[&dyn Puzzle], options: &Options) -> ExitCode {
+    let failures = match options.command {
+        Command::New { day } => return new(day),
+        Command::Run => selected(puzzles, options).map(|selected| solve(&selected, options)),
+        Command::Bench { runs } => {
+            selected(puzzles, options).map(|selected| bench(&selected, options, runs))
+        }
+        Command::Verify => selected(puzzles, options).map(|selected| verify(&selected, options)),
+    };
+
+    match failures {
+        None => ExitCode::from(2),
+        Some(0) => ExitCode::SUCCESS,
+        Some(_) => ExitCode::FAILURE,
+    }
+}
+
+/// Like [`select`], but reports the available days when the selected one is not registered.
+fn selected<'a>(
+    puzzles: &[&'a dyn Puzzle],
+    options: &Options,
+) -> Option<Vec<(&'a dyn Puzzle, Vec<Part>)>> {
+    let selected = select(puzzles, &options.selection);
+
+    if selected.is_none() {
+        let available = puzzles
+            .iter()
+            .map(|puzzle| puzzle.day().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if let Selection::Day { day, .. } = options.selection {
+            eprintln!("Day {} is not implemented (available: {})", day, available);
+        }
+    }
+
+    selected
+}
+
+/// Parses the input of a puzzle, reporting why it could not be parsed. A panic while parsing is
+/// treated like an error.
+fn parse(puzzle: &dyn Puzzle, lines: &[String], path: &Path) -> Option<Box<dyn Parsed>> {
+    match panic::catch_unwind(AssertUnwindSafe(|| puzzle.parse(lines))) {
+        Ok(Ok(parsed)) => Some(parsed),
+        Ok(Err(e)) => {
+            eprintln!("Day {} : {}", puzzle.day(), e.in_file(path));
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+/// Solves the given parts of a single puzzle. A part fails when its input could not be read or
+/// when parsing or solving panicked.
+fn records(puzzle: &dyn Puzzle, parts: &[Part], options: &Options) -> Vec<Record> {
+    let day = puzzle.day();
+    let path = input::path(day, options.input.as_deref());
+
+    let parsed = input::load(&path)
+        .map_err(|e| eprintln!("Day {} : {}", day, e))
+        .ok()
+        .and_then(|lines| parse(puzzle, &lines, &path));
+
+    parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let solved = parsed.as_ref().and_then(|parsed| {
+                panic::catch_unwind(AssertUnwindSafe(|| parsed.solve(part))).ok()
+            });
+
+            Record {
+                day,
+                part,
+                status: match &solved {
+                    None => Status::Failed,
+                    Some(None) => Status::Unsolved,
+                    Some(Some(_)) => Status::Ok,
+                },
+                answer: solved.flatten(),
+                expected: None,
+                elapsed: start.elapsed(),
+                input: path.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Prints the answers, returns the number of failed parts.
+fn solve(selected: &[(&dyn Puzzle, Vec<Part>)], options: &Options) -> usize {
+    let mut failures = 0;
+
+    if options.format == Format::Csv {
+        println!("{}", CSV_HEADER);
+    }
+
+    for (puzzle, parts) in selected {
+        for record in records(*puzzle, parts, options) {
+            if record.status == Status::Failed {
+                failures += 1;
+            }
+            record.print(options.format);
+        }
+    }
+
+    failures
+}
+
+/// Compares the answers with the recorded ones, returns the number of parts that failed or gave
+/// a different answer. Parts without a recorded answer are reported but do not count as failures,
+/// an unsolved part only fails when there is a recorded answer for it. Without readable answers
+/// nothing is run and verify fails.
+fn verify(selected: &[(&dyn Puzzle, Vec<Part>)], options: &Options) -> usize {
+    let path = answers::path();
+
+    let recorded = match std::fs::read_to_string(&path) {
+        Ok(s) => match s.parse::<Answers>() {
+            Ok(recorded) => recorded,
+            Err(e) => {
+                eprintln!("{}", e.in_file(&path));
+                return 1;
+            }
+        },
+        Err(e) => {
+            eprintln!("cannot read answers '{}': {}", path.display(), e);
+            return 1;
+        }
+    };
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    if options.format == Format::Csv {
+        println!("{}", CSV_HEADER);
+    }
+
+    for (puzzle, parts) in selected {
+        for mut record in records(*puzzle, parts, options) {
+            record.expected = recorded.get(record.day, record.part).map(str::to_string);
+
+            record.status = match (record.status, &record.answer, &record.expected) {
+                (Status::Failed, _, _) => Status::Failed,
+                (_, Some(answer), Some(expected)) if answer == expected => Status::Pass,
+                (_, _, Some(_)) => Status::Fail,
+                (Status::Unsolved, _, None) => Status::Unsolved,
+                (_, _, None) => Status::Missing,
+            };
+
+            match record.status {
+                Status::Pass => passed += 1,
+                Status::Missing | Status::Unsolved => missing += 1,
+                _ => failed += 1,
+            }
+
+            record.print(options.format);
+        }
+    }
+
+    let summary = format!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if options.format == Format::Text {
+        println!("{}", summary);
+    } else {
+        eprintln!("{}", summary);
+    }
+
+    failed
+}
+
+/// Times parsing and every part `runs` times and prints min/median/max, returns the number of
+/// failed parts. The input is read once, only parsing and solving are measured.
+fn bench(selected: &[(&dyn Puzzle, Vec<Part>)], options: &Options, runs: usize) -> usize {
+    let mut failures = 0;
+
+    for (puzzle, parts) in selected {
+        let day = puzzle.day();
+
+        let path = input::path(day, options.input.as_deref());
+        let lines = match input::load(&path) {
+            Ok(lines) => lines,
+            Err(e) => {
+                eprintln!("Day {} : {}", day, e);
+                failures += parts.len();
+                continue;
+            }
+        };
+
+        let mut parse_samples = Vec::with_capacity(runs);
+        let mut part_samples = vec![Vec::with_capacity(runs); parts.len()];
+
+        let completed = (0..runs).all(|_| {
+            let start = Instant::now();
+            let Some(parsed) = parse(*puzzle, &lines, &path) else {
+                return false;
+            };
+            parse_samples.push(start.elapsed());
+
+            parts
+                .iter()
+                .zip(part_samples.iter_mut())
+                .all(|(&part, samples)| {
+                    let start = Instant::now();
+                    let solved =
+                        panic::catch_unwind(AssertUnwindSafe(|| parsed.solve(part))).is_ok();
+                    samples.push(start.elapsed());
+                    solved
+                })
+        });
+
+        if !completed {
+            eprintln!("Day {} : failed", day);
+            failures += parts.len();
+            continue;
+        }
+
+        println!(
+            "{:<18}{:>12} {:>12} {:>12}",
+            format!("Day {} ({} runs)", day, runs),
+            "min",
+            "median",
+            "max"
+        );
+        println!("  {:<16}{}", "parse", Timing::from(&mut parse_samples));
+        for (part, samples) in parts.iter().zip(part_samples.iter_mut()) {
+            println!("  part {:<11}{}", part, Timing::from(samples));
+        }
+    }
+
+    failures
+}
+
+/// Generates a new day, printing the files that were touched.
+fn new(day: u32) -> ExitCode {
+    match scaffold::new_day(day) {
+        Ok(files) => {
+            files.iter().for_each(|file| println!("{}", file.display()));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::cli::Format;
+use advent::solution::Part;
+
+/// Outcome of a single part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    /// Solved, nothing to compare against.
+    Ok,
+    /// The input could not be read, or parsing or solving panicked.
+    Failed,
+    /// Same answer as recorded.
+    Pass,
+    /// Different answer than recorded.
+    Fail,
+    /// Solved, but there is no recorded answer.
+    Missing,
+    /// The part is not marked as [`SOLVED`](advent::solution::Solution::SOLVED).
+    Unsolved,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Failed => "failed",
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Missing => "missing",
+            Status::Unsolved => "unsolved",
+        }
+    }
+}
+
+/// Everything known about one part after running it.
+#[derive(Debug)]
+pub struct Record {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Option<String>,
+    /// Recorded answer, only filled in by verify.
+    pub expected: Option<String>,
+    /// Time spent solving the part, parsing the input is not included.
+    pub elapsed: Duration,
+    pub input: PathBuf,
+    pub status: Status,
+}
+
+pub const CSV_HEADER: &str = "day,part,answer,expected,elapsed_ns,input,status";
+
+impl Record {
+    pub fn text(&self) -> String {
+        let answer = self.answer.as_deref().unwrap_or_default();
+        let expected = self.expected.as_deref().unwrap_or_default();
+
+        let outcome = match self.status {
+            Status::Ok => answer.to_string(),
+            Status::Failed => "failed".to_string(),
+            Status::Pass => "pass".to_string(),
+            Status::Fail if self.answer.is_none() => {
+                format!("FAIL, expected {} but it is unsolved", expected)
+            }
+            Status::Fail => format!("FAIL, expected {} but got {}", expected, answer),
+            Status::Missing => format!("missing, got {}", answer),
+            Status::Unsolved => "unsolved".to_string(),
+        };
+
+        format!("Day {} part {} : {}", self.day, self.part, outcome)
+    }
+
+    pub fn json(&self) -> String {
+        let optional = |s: &Option<String>| s.as_deref().map_or("null".to_string(), json_string);
+
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"expected\":{},\"elapsed_ns\":{},\"input\":{},\"status\":\"{}\"}}",
+            self.day,
+            self.part,
+            optional(&self.answer),
+            optional(&self.expected),
+            self.elapsed.as_nanos(),
+            json_string(&self.input.to_string_lossy()),
+            self.status.as_str()
+        )
+    }
+
+    pub fn csv(&self) -> String {
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            csv_field(self.answer.as_deref().unwrap_or_default()),
+            csv_field(self.expected.as_deref().unwrap_or_default()),
+            self.elapsed.as_nanos().to_string(),
+            csv_field(&self.input.to_string_lossy()),
+            self.status.as_str().to_string(),
+        ]
+        .join(",")
+    }
+
+    /// Prints the record in the requested format. Failures in text format go to stderr.
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text if self.status == Status::Failed => eprintln!("{}", self.text()),
+            Format::Text => println!("{}", self.text()),
+            Format::Json => println!("{}", self.json()),
+            Format::Csv => println!("{}", self.csv()),
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Record {
+        Record {
+            day: 5,
+            part: Part::Two,
+            answer: Some("123".to_string()),
+            expected: None,
+            elapsed: Duration::from_micros(15),
+            input: PathBuf::from("src/day5/input.txt"),
+            status: Status::Ok,
+        }
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(record().text(), "Day 5 part 2 : 123");
+
+        let unsolved = Record {
+            answer: None,
+            status: Status::Unsolved,
+            ..record()
+        };
+        assert_eq!(unsolved.text(), "Day 5 part 2 : unsolved");
+        assert_eq!(
+            Record {
+                expected: Some("7".to_string()),
+                status: Status::Fail,
+                ..unsolved
+            }
+            .text(),
+            "Day 5 part 2 : FAIL, expected 7 but it is unsolved"
+        );
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            record().json(),
+            r#"{"day":5,"part":2,"answer":"123","expected":null,"elapsed_ns":15000,"input":"src/day5/input.txt","status":"ok"}"#
+        );
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(record().csv(), "5,2,123,,15000,src/day5/input.txt,ok");
+        assert_eq!(csv_field("a,\"b\""), r#""a,""b""""#);
+    }
+}
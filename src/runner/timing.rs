@@ -0,0 +1,66 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Summary of a series of measurements.
+#[derive(Debug, PartialEq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl From<&mut Vec<Duration>> for Timing {
+    /// Sorts the samples in place, there must be at least one.
+    fn from(samples: &mut Vec<Duration>) -> Self {
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Timing {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>12.3?} {:>12.3?} {:>12.3?}",
+            self.min, self.median, self.max
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn odd() {
+        let mut samples = [5, 1, 3].map(Duration::from_millis).to_vec();
+
+        assert_eq!(
+            Timing::from(&mut samples),
+            Timing {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            }
+        );
+    }
+
+    #[test]
+    fn even() {
+        let mut samples = [4, 1, 2, 8].map(Duration::from_millis).to_vec();
+
+        assert_eq!(Timing::from(&mut samples).median, Duration::from_millis(3));
+    }
+}
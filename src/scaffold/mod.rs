@@ -0,0 +1,190 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use advent::input;
+
+/// The template a new day starts from, `DayX` and `X` are replaced by the day.
+const TEMPLATE: &str = include_str!("../dayx/mod.rs");
+
+/// The file declaring the day modules and the registry of solutions.
+const REGISTRY: &str = "lib.rs";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    Registry(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "'{}' already exists", path.display()),
+            ScaffoldError::Registry(path) => {
+                write!(f, "cannot find the day modules in '{}'", path.display())
+            }
+            ScaffoldError::Io(path, e) => write!(f, "'{}': {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+fn src() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+/// Creates `src/day<N>` from the template with an empty input and example input, and adds the
+/// day to the registry. Returns the files that were created or changed.
+pub fn new_day(day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let dir = src().join(format!("day{}", day));
+    let module = dir.join("mod.rs");
+    let example = dir.join("example.txt");
+    let input = input::path(day, None);
+    let registry = src().join(REGISTRY);
+
+    if module.exists() {
+        return Err(ScaffoldError::Exists(module));
+    }
+
+    let registered = fs::read_to_string(&registry)
+        .map_err(|e| ScaffoldError::Io(registry.clone(), e))
+        .and_then(|s| register(&s, day).ok_or(ScaffoldError::Registry(registry.clone())))?;
+
+    fs::create_dir_all(&dir).map_err(|e| ScaffoldError::Io(dir.clone(), e))?;
+    write(&module, &instantiate(day))?;
+    write(&example, "")?;
+    if !input.exists() {
+        if let Some(parent) = input.parent() {
+            fs::create_dir_all(parent).map_err(|e| ScaffoldError::Io(parent.to_path_buf(), e))?;
+        }
+        write(&input, "")?;
+    }
+    write(&registry, &registered)?;
+
+    Ok(vec![module, example, input, registry])
+}
+
+fn instantiate(day: u32) -> String {
+    TEMPLATE
+        .replace("DayX", &format!("Day{}", day))
+        .replace("const DAY: u32 = X;", &format!("const DAY: u32 = {};", day))
+}
+
+/// Day number of a line like `pub mod day5;` or `    &day5::Day5,`.
+fn declared_day(line: &str, prefix: &str) -> Option<u32> {
+    let rest = line.strip_prefix(prefix)?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(rest, |end| &rest[..end]);
+    digits.parse().ok()
+}
+
+/// Adds the module declaration and registry entry for `day` to `source`, both after the last
+/// day before it. `None` if either list cannot be found or the day is already declared.
+fn register(source: &str, day: u32) -> Option<String> {
+    let lines: Vec<&str> = source.lines().collect();
+
+    let insert_after = |prefix: &str| -> Option<usize> {
+        let declared: Vec<(usize, u32)> = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| declared_day(line, prefix).map(|d| (i, d)))
+            .collect();
+
+        if declared.is_empty() || declared.iter().any(|&(_, d)| d == day) {
+            return None;
+        }
+
+        Some(
+            declared
+                .iter()
+                .filter(|&&(_, d)| d < day)
+                .map(|&(i, _)| i)
+                .next_back()
+                .map_or(declared[0].0, |i| i + 1),
+        )
+    };
+
+    let module = insert_after("pub mod day")?;
+    let entry = insert_after("    &day")?;
+
+    let mut result: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
+    // Insert the later one first so the earlier index stays valid.
+    let mut inserts = [
+        (module, format!("pub mod day{};", day)),
+        (entry, format!("    &day{}::Day{},", day, day)),
+    ];
+    inserts.sort_by_key(|(i, _)| std::cmp::Reverse(*i));
+    for (i, line) in inserts {
+        result.insert(i, line);
+    }
+
+    Some(result.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "pub mod common;
+pub mod day1;
+pub mod day2;
+pub mod day4;
+pub mod error;
+
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day4::Day4,
+];
+";
+
+    #[test]
+    fn register_in_order() {
+        assert_eq!(
+            register(SOURCE, 3).unwrap(),
+            "pub mod common;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod error;
+
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+];
+"
+        );
+
+        let last = register(SOURCE, 12).unwrap();
+        assert!(last.contains("pub mod day4;\npub mod day12;\npub mod error;"));
+        assert!(last.contains("    &day4::Day4,\n    &day12::Day12,\n];"));
+    }
+
+    #[test]
+    fn register_existing() {
+        assert_eq!(register(SOURCE, 2), None);
+        assert_eq!(register("fn main() {}", 2), None);
+    }
+
+    #[test]
+    fn template() {
+        let source = instantiate(7);
+
+        assert!(source.contains("pub struct Day7;"));
+        assert!(source.contains("impl Solution for Day7 {"));
+        assert!(source.contains("const DAY: u32 = 7;"));
+        assert!(!source.contains("DayX"));
+        assert!(!source.contains("todo!"));
+    }
+}
@@ -0,0 +1,167 @@
+use std::fmt::{self, Display};
+
+use crate::error::Result;
+
+/// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// Placeholder answer for a part that has not been solved yet, see [`Solution::SOLVED`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("unsolved")
+    }
+}
+
+/// A puzzle day. The input lines are parsed once and the result is shared by both parts, so
+/// malformed input is reported by `parse` and the parts can assume it is valid.
+pub trait Solution {
+    const DAY: u32;
+    /// Whether part 1 and part 2 are solved, the runner reports the answer of an unsolved part
+    /// as unsolved instead of printing it.
+    const SOLVED: [bool; 2] = [true, true];
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &[String]) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+/// Type erased view of a [`Solution`] so that all days can be kept in a single registry.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u32;
+    fn parse(&self, input: &[String]) -> Result<Box<dyn Parsed>>;
+}
+
+/// Parsed input of a [`Puzzle`], ready to be solved.
+pub trait Parsed {
+    /// The answer to `part`, `None` when it is not [`Solution::SOLVED`].
+    fn solve(&self, part: Part) -> Option<String>;
+}
+
+struct Prepared<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for Prepared<S> {
+    fn solve(&self, part: Part) -> Option<String> {
+        match part {
+            Part::One => S::SOLVED[0].then(|| S::part1(&self.0).to_string()),
+            Part::Two => S::SOLVED[1].then(|| S::part2(&self.0).to_string()),
+        }
+    }
+}
+
+impl<S> Puzzle for S
+where
+    S: Solution + Sync + 'static,
+{
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &[String]) -> Result<Box<dyn Parsed>> {
+        Ok(Box::new(Prepared::<S>(S::parse(input)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u32 = 0;
+
+        type Input = Vec<i64>;
+        type Answer1 = i64;
+        type Answer2 = usize;
+
+        fn parse(input: &[String]) -> Result<Self::Input> {
+            input
+                .iter()
+                .map(|s| s.parse().map_err(|_| Error::parse("not a number")))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Self::Answer1 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> Self::Answer2 {
+            input.len()
+        }
+    }
+
+    #[test]
+    fn erased() {
+        let puzzle: &dyn Puzzle = &Sum;
+        let parsed = puzzle
+            .parse(&["1".to_string(), "2".to_string(), "39".to_string()])
+            .unwrap();
+
+        assert_eq!(puzzle.day(), 0);
+        assert_eq!(parsed.solve(Part::One).as_deref(), Some("42"));
+        assert_eq!(parsed.solve(Part::Two).as_deref(), Some("3"));
+    }
+
+    struct Todo;
+
+    impl Solution for Todo {
+        const DAY: u32 = 0;
+        const SOLVED: [bool; 2] = [true, false];
+
+        type Input = ();
+        type Answer1 = usize;
+        type Answer2 = Unsolved;
+
+        fn parse(_input: &[String]) -> Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input) -> Self::Answer1 {
+            1
+        }
+
+        fn part2(_input: &Self::Input) -> Self::Answer2 {
+            Unsolved
+        }
+    }
+
+    #[test]
+    fn unsolved() {
+        let parsed = Todo.parse(&[]).unwrap();
+
+        assert_eq!(parsed.solve(Part::One).as_deref(), Some("1"));
+        assert_eq!(parsed.solve(Part::Two), None);
+    }
+
+    #[test]
+    fn erased_error() {
+        let puzzle: &dyn Puzzle = &Sum;
+
+        assert!(puzzle.parse(&["x".to_string()]).is_err());
+    }
+}